@@ -1,97 +1,843 @@
-#[derive(Debug)]
+use crate::{
+    error::Error,
+    format,
+    growth_policy::GrowthPolicy,
+    interfaces::{Deque, List, Queue},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ops::{Index, IndexMut, Range, RangeBounds},
+};
+
 pub struct ArrayDeque<T> {
-    a: Box<[T]>,
+    a: Box<[MaybeUninit<T>]>,
     j: usize,
     n: usize,
+    policy: GrowthPolicy,
 }
 
-impl<T: Default> ArrayDeque<T> {
+impl<T> ArrayDeque<T> {
     pub fn new() -> Self {
+        Self::with_capacity(1)
+    }
+
+    /// Creates an empty deque whose backing array has room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            a: Box::new([Default::default(); 1]),
+            a: allocate(std::cmp::max(1, capacity)),
             j: 0,
             n: 0,
+            policy: GrowthPolicy::default(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.policy
+    }
+
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.policy = policy;
+    }
+
+    /// Grows the backing array, if needed, so that `additional` more elements
+    /// fit without resizing.
+    ///
+    /// Panics if the new capacity overflows; see [`try_reserve`](Self::try_reserve).
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(e) = self.try_reserve(additional) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+        let required = self
+            .n
+            .checked_add(additional)
+            .ok_or(Error::CapacityExceeded)?;
+        if required > self.a.len() {
+            let capacity = self.policy.grow::<MaybeUninit<T>>(self.a.len(), required)?;
+            self.resize(capacity);
         }
+        Ok(())
+    }
+
+    /// Shrinks the backing array to hold exactly `size()` elements (at least one).
+    pub fn shrink_to_fit(&mut self) {
+        self.resize(std::cmp::max(1, self.n));
     }
 
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = as_slices(&self.a, self.j, self.n);
+        Iter::new(head, tail)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = as_mut_slices(&mut self.a, self.j, self.n);
+        IterMut::new(head, tail)
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
     pub fn get(&self, i: usize) -> Option<&T> {
         if i >= self.n {
             return None;
         }
-        Some(&self.a[(i + self.j) % self.a.len()])
+        // SAFETY: the `n` slots starting at `j` are initialized.
+        Some(unsafe { self.a[(i + self.j) % self.a.len()].assume_init_ref() })
     }
 
-    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
         if i >= self.n {
             return None;
         }
-        let y = std::mem::replace(&mut self.a[(i + self.j) % self.a.len()], x);
-        Some(y)
+        // SAFETY: the `n` slots starting at `j` are initialized.
+        Some(unsafe { self.a[(i + self.j) % self.a.len()].assume_init_mut() })
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
     }
 
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        // SAFETY: the `n` slots starting at `j` are initialized.
+        let y = unsafe { self.a[(i + self.j) % self.a.len()].assume_init_mut() };
+        Ok(std::mem::replace(y, x))
+    }
+
+    /// Inserts `x` at index `i`, shifting whichever side of `i` is shorter.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
     pub fn add(&mut self, i: usize, x: T) {
-        if self.n == self.a.len() {
-            self.resize();
-        }
-        if i < self.n / 2 {
-            self.j = (self.a.len() + self.j - 1) % self.a.len();
-            if i > 0 {
-                for k in 0..i - 1 {
-                    self.a
-                        .swap((self.j + k) % self.a.len(), (self.j + k + 1) % self.a.len());
-                }
-            }
-        } else {
-            for k in (i + 1..self.n + 1).rev() {
-                self.a.swap(
-                    (self.j + k) % self.a.len(),
-                    (self.j + k - 1 + self.a.len()) % self.a.len(),
-                );
-            }
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
         }
+    }
 
-        self.a[(self.j + i) % self.a.len()] = x;
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        self.try_reserve(1)?;
+        self.j = open_gap(&mut self.a, self.j, self.n, i, 1);
+        self.a[(self.j + i) % self.a.len()] = MaybeUninit::new(x);
         self.n += 1;
+        Ok(())
     }
 
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
     pub fn remove(&mut self, i: usize) -> Option<T> {
-        if self.get(i).is_none() {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        // SAFETY: slot `j + i` is initialized; the shifts below move the hole
+        // it leaves behind out of the `n - 1` remaining slots.
+        let x = unsafe { self.a[(self.j + i) % self.a.len()].assume_init_read() };
+        self.j = close_gap(&mut self.a, self.j, self.n, i, i + 1);
+        self.n -= 1;
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
+        }
+        Ok(x)
+    }
+
+    /// Inserts the elements of `iter` at index `i`, resizing at most once and
+    /// shifting each element on the shorter side of `i` once.
+    ///
+    /// Panics if `i > size()`.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, i: usize, iter: I) {
+        if let Err(e) = Error::check_insert(i, self.n) {
+            panic!("{}", e);
+        }
+        let xs = iter.into_iter().collect::<Vec<_>>();
+        let k = xs.len();
+        self.reserve(k);
+        self.j = open_gap(&mut self.a, self.j, self.n, i, k);
+        let len = self.a.len();
+        for (m, x) in xs.into_iter().enumerate() {
+            self.a[(self.j + i + m) % len] = MaybeUninit::new(x);
+        }
+        self.n += k;
+    }
+
+    /// Removes the elements in `range`, shifting each element on the shorter
+    /// side of the gap once.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range);
+    }
+
+    /// Removes the elements in `range` and returns them in order.
+    ///
+    /// The elements are removed immediately, even if the iterator is not consumed.
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let Range { start, end } =
+            Error::check_range(range, self.n).unwrap_or_else(|e| panic!("{}", e));
+        let len = self.a.len();
+        let d = end - start;
+        let xs = (start..end)
+            // SAFETY: the slots in `range` are initialized; the shifts below
+            // move the gap they leave out of the `n - d` remaining slots.
+            .map(|m| unsafe { self.a[(self.j + m) % len].assume_init_read() })
+            .collect::<Vec<_>>();
+        self.j = close_gap(&mut self.a, self.j, self.n, start, end);
+        self.n -= d;
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
+        }
+        Drain::new(xs)
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.n.checked_sub(1)?)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.n.checked_sub(1)?)
+    }
+
+    /// Adds `x` at the front in amortized O(1) time.
+    pub fn push_front(&mut self, x: T) {
+        self.reserve(1);
+        self.j = (self.j + self.a.len() - 1) % self.a.len();
+        self.a[self.j] = MaybeUninit::new(x);
+        self.n += 1;
+    }
+
+    /// Adds `x` at the back in amortized O(1) time.
+    pub fn push_back(&mut self, x: T) {
+        self.reserve(1);
+        self.a[(self.j + self.n) % self.a.len()] = MaybeUninit::new(x);
+        self.n += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        // SAFETY: slot `j` holds the front element, and advancing `j` below
+        // marks it uninitialized again.
+        let x = unsafe { self.a[self.j].assume_init_read() };
+        self.j = (self.j + 1) % self.a.len();
+        self.n -= 1;
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
+        }
+        Some(x)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.n == 0 {
             return None;
         }
-        let x = std::mem::replace(&mut self.a[(self.j + i) % self.a.len()], Default::default());
-        if i < self.n / 2 {
-            for k in (1..i + 1).rev() {
-                self.a.swap(
-                    (self.j + k) % self.a.len(),
-                    (self.j + k - 1 + self.a.len()) % self.a.len(),
-                );
+        self.n -= 1;
+        // SAFETY: slot `j + n` held the back element, and decrementing `n`
+        // above marks it uninitialized again.
+        let x = unsafe { self.a[(self.j + self.n) % self.a.len()].assume_init_read() };
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
+        }
+        Some(x)
+    }
+
+    /// Removes every element, keeping the backing array.
+    pub fn clear(&mut self) {
+        let (j, n) = (self.j, self.n);
+        self.j = 0;
+        self.n = 0;
+        for k in 0..n {
+            // SAFETY: the `n` slots starting at `j` were initialized, and are
+            // no longer reachable now that `n` is zero.
+            unsafe { self.a[(j + k) % self.a.len()].assume_init_drop() }
+        }
+    }
+
+    /// Returns the elements as two slices: the run from `j` to the end of the
+    /// backing array, then the part that wraps around to its start.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        as_slices(&self.a, self.j, self.n)
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        as_mut_slices(&mut self.a, self.j, self.n)
+    }
+
+    /// Rearranges the backing array so the elements no longer wrap around,
+    /// and returns them as one slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.j + self.n > self.a.len() {
+            self.a.rotate_left(self.j);
+            self.j = 0;
+        }
+        as_mut_slices(&mut self.a, self.j, self.n).0
+    }
+
+    /// Swaps the elements at indices `i` and `k`.
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn swap(&mut self, i: usize, k: usize) {
+        for &index in &[i, k] {
+            if let Err(e) = Error::check_index(index, self.n) {
+                panic!("{}", e);
             }
-            self.j = (self.j + 1) % self.a.len();
+        }
+        self.a
+            .swap((self.j + i) % self.a.len(), (self.j + k) % self.a.len());
+    }
+
+    /// Rotates the deque `k` places to the left, so the element at index `k`
+    /// becomes the front, moving `min(k, size() - k)` elements.
+    ///
+    /// Panics if `k > size()`.
+    pub fn rotate_left(&mut self, k: usize) {
+        if let Err(e) = Error::check_insert(k, self.n) {
+            panic!("{}", e);
+        }
+        if k <= self.n - k {
+            self.wrap_front(k);
         } else {
-            for k in i..self.n - 1 {
-                self.a
-                    .swap((self.j + k) % self.a.len(), (self.j + k + 1) % self.a.len());
+            self.wrap_back(self.n - k);
+        }
+    }
+
+    /// Rotates the deque `k` places to the right, so the element at index
+    /// `size() - k` becomes the front, moving `min(k, size() - k)` elements.
+    ///
+    /// Panics if `k > size()`.
+    pub fn rotate_right(&mut self, k: usize) {
+        if let Err(e) = Error::check_insert(k, self.n) {
+            panic!("{}", e);
+        }
+        if k <= self.n - k {
+            self.wrap_back(k);
+        } else {
+            self.wrap_front(self.n - k);
+        }
+    }
+
+    /// Moves the first `k` elements to the back, one at a time, into the
+    /// slots just past the last element.
+    fn wrap_front(&mut self, k: usize) {
+        let len = self.a.len();
+        for m in 0..k {
+            self.a.swap((self.j + m) % len, (self.j + self.n + m) % len);
+        }
+        self.j = (self.j + k) % len;
+    }
+
+    /// Moves the last `k` elements to the front, one at a time, into the
+    /// slots just before the first element.
+    fn wrap_back(&mut self, k: usize) {
+        let len = self.a.len();
+        for m in 1..=k {
+            self.a
+                .swap((self.j + self.n + len - m) % len, (self.j + len - m) % len);
+        }
+        self.j = (self.j + len - k) % len;
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        let mut old_a = std::mem::replace(&mut self.a, allocate(capacity));
+        for k in 0..self.n {
+            self.a[k] = std::mem::replace(
+                &mut old_a[(self.j + k) % old_a.len()],
+                MaybeUninit::uninit(),
+            );
+        }
+        self.j = 0;
+    }
+}
+
+/// Allocates `len` slots without initializing any of them, in O(1); also used
+/// for the blocks of block-based lists.
+pub(crate) fn allocate<T>(len: usize) -> Box<[MaybeUninit<T>]> {
+    Box::new_uninit_slice(len)
+}
+
+/// Makes room for `k` elements at index `i` of the `n` elements starting at
+/// `j` in the circular array `a`, shifting whichever side of `i` is shorter
+/// once. Returns the new `j`; the `k` slots from index `i` are then free.
+/// There must be at least `k` free slots.
+pub(crate) fn open_gap<T>(
+    a: &mut [MaybeUninit<T>],
+    j: usize,
+    n: usize,
+    i: usize,
+    k: usize,
+) -> usize {
+    let len = a.len();
+    if i < n / 2 {
+        let j = (j + len - k) % len;
+        for m in 0..i {
+            a.swap((j + m) % len, (j + m + k) % len);
+        }
+        j
+    } else {
+        for m in (i..n).rev() {
+            a.swap((j + m) % len, (j + m + k) % len);
+        }
+        j
+    }
+}
+
+/// Closes the gap left by moving the elements at indices `start..end` out of
+/// the `n` starting at `j`, shifting whichever side of it is shorter once.
+/// Returns the new `j`; the other `n - (end - start)` elements then follow it.
+pub(crate) fn close_gap<T>(
+    a: &mut [MaybeUninit<T>],
+    j: usize,
+    n: usize,
+    start: usize,
+    end: usize,
+) -> usize {
+    let len = a.len();
+    let d = end - start;
+    if start < n - end {
+        for m in (0..start).rev() {
+            a.swap((j + m) % len, (j + m + d) % len);
+        }
+        (j + d) % len
+    } else {
+        for m in end..n {
+            a.swap((j + m) % len, (j + m - d) % len);
+        }
+        j
+    }
+}
+
+pub(crate) fn as_slices<T>(a: &[MaybeUninit<T>], j: usize, n: usize) -> (&[T], &[T]) {
+    let (tail, head) = a.split_at(j);
+    let k = std::cmp::min(n, head.len());
+    // SAFETY: the `n` slots starting at `j` (wrapping around) are initialized.
+    unsafe {
+        (
+            &*(&head[..k] as *const [MaybeUninit<T>] as *const [T]),
+            &*(&tail[..n - k] as *const [MaybeUninit<T>] as *const [T]),
+        )
+    }
+}
+
+pub(crate) fn as_mut_slices<T>(
+    a: &mut [MaybeUninit<T>],
+    j: usize,
+    n: usize,
+) -> (&mut [T], &mut [T]) {
+    let (tail, head) = a.split_at_mut(j);
+    let k = std::cmp::min(n, head.len());
+    // SAFETY: the `n` slots starting at `j` (wrapping around) are initialized.
+    unsafe {
+        (
+            &mut *(&mut head[..k] as *mut [MaybeUninit<T>] as *mut [T]),
+            &mut *(&mut tail[..n - k] as *mut [MaybeUninit<T>] as *mut [T]),
+        )
+    }
+}
+
+/// A circular backing array, showing the `n` slots starting at `j` as `Some`
+/// and the rest as `None`.
+pub(crate) struct Slots<'a, T> {
+    a: &'a [MaybeUninit<T>],
+    j: usize,
+    n: usize,
+}
+
+impl<'a, T> Slots<'a, T> {
+    pub(crate) fn new(a: &'a [MaybeUninit<T>], j: usize, n: usize) -> Self {
+        Self { a, j, n }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Slots<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.a.len();
+        let slots = self.a.iter().enumerate().map(|(k, x)| {
+            if (k + len - self.j) % len < self.n {
+                // SAFETY: the `n` slots starting at `j` are initialized.
+                Some(unsafe { x.assume_init_ref() })
+            } else {
+                None
             }
+        });
+        f.debug_list().entries(slots).finish()
+    }
+}
+
+impl<T> Drop for ArrayDeque<T> {
+    fn drop(&mut self) {
+        for k in 0..self.n {
+            // SAFETY: the `n` slots starting at `j` are initialized.
+            unsafe { self.a[(self.j + k) % self.a.len()].assume_init_drop() }
         }
+    }
+}
 
-        self.n -= 1;
-        if self.a.len() >= 3 * self.n {
-            self.resize();
+impl<T> Default for ArrayDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for ArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("ArrayDeque")
+                .field("a", &Slots::new(&self.a, self.j, self.n))
+                .field("j", &self.j)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayDeque<T> {
+    fn clone(&self) -> Self {
+        let mut array_deque = Self::with_capacity(self.a.len());
+        array_deque.set_growth_policy(self.policy);
+        array_deque.extend(self.iter().cloned());
+        array_deque
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayDeque<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayDeque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayDeque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for ArrayDeque<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayDeque<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayDeque<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<ArrayDeque<T>> for Vec<T> {
+    fn from(xs: ArrayDeque<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for ArrayDeque<T> {
+    fn size(&self) -> usize {
+        ArrayDeque::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        ArrayDeque::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        ArrayDeque::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        ArrayDeque::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        ArrayDeque::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        ArrayDeque::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        ArrayDeque::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        ArrayDeque::try_remove(self, i)
+    }
+}
+
+impl<T> Queue<T> for ArrayDeque<T> {
+    fn push_back(&mut self, x: T) {
+        ArrayDeque::push_back(self, x)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        ArrayDeque::pop_front(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        ArrayDeque::front(self)
+    }
+}
+
+impl<T> Deque<T> for ArrayDeque<T> {
+    fn push_front(&mut self, x: T) {
+        ArrayDeque::push_front(self, x)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        ArrayDeque::pop_back(self)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        ArrayDeque::back(self)
+    }
+}
+
+impl<T> MemoryUsage for ArrayDeque<T> {
+    fn heap_bytes(&self) -> usize {
+        self.a.len() * std::mem::size_of::<MaybeUninit<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.a.len()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.a.len() - self.n
+    }
+}
+
+pub struct Iter<'a, T> {
+    head: std::slice::Iter<'a, T>,
+    tail: std::slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(head: &'a [T], tail: &'a [T]) -> Self {
+        Self {
+            head: head.iter(),
+            tail: tail.iter(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.head.next() {
+            Some(x) => Some(x),
+            None => self.tail.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.tail.next_back() {
+            Some(x) => Some(x),
+            None => self.head.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    head: std::slice::IterMut<'a, T>,
+    tail: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(head: &'a mut [T], tail: &'a mut [T]) -> Self {
+        Self {
+            head: head.iter_mut(),
+            tail: tail.iter_mut(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.head.next() {
+            Some(x) => Some(x),
+            None => self.tail.next(),
         }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.head.len() + self.tail.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.tail.next_back() {
+            Some(x) => Some(x),
+            None => self.head.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub use crate::array_stack::Drain;
+
+pub struct IntoIter<T> {
+    deque: ArrayDeque<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let deque = &mut self.deque;
+        if deque.n == 0 {
+            return None;
+        }
+        // SAFETY: slot `j` holds the front element, and advancing `j` below
+        // marks it uninitialized again.
+        let x = unsafe { deque.a[deque.j].assume_init_read() };
+        deque.j += 1;
+        if deque.j == deque.a.len() {
+            deque.j = 0;
+        }
+        deque.n -= 1;
         Some(x)
     }
 
-    fn resize(&mut self) {
-        let new_a = std::iter::repeat_with(|| Default::default())
-            .take(std::cmp::max(1, 2 * self.n))
-            .collect::<Vec<_>>()
-            .into_boxed_slice();
-        let mut old_a = std::mem::replace(&mut self.a, new_a);
-        old_a.rotate_left(self.j);
-        for (i, ai) in old_a.into_vec().into_iter().take(self.n).enumerate() {
-            self.a[i] = ai;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.n, Some(self.deque.n))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let deque = &mut self.deque;
+        if deque.n == 0 {
+            return None;
         }
-        self.j = 0;
+        let mut k = deque.j + deque.n - 1;
+        if k >= deque.a.len() {
+            k -= deque.a.len();
+        }
+        // SAFETY: slot `k` holds the back element, and decrementing `n` below
+        // marks it uninitialized again.
+        let x = unsafe { deque.a[k].assume_init_read() };
+        deque.n -= 1;
+        Some(x)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for ArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for ArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_many(self.n, iter);
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for ArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array_deque = Self::new();
+        array_deque.extend(iter);
+        array_deque
     }
 }
 
@@ -141,4 +887,194 @@ mod test {
         array_deque.add(0, 10);
         assert_eq!(array_deque.get(0), Some(&10));
     }
+
+    #[test]
+    fn array_deque_moves_and_drops_values() {
+        let counter = std::rc::Rc::new(());
+        let mut array_deque = ArrayDeque::new();
+        for i in 0..10 {
+            array_deque.add(i / 2, std::rc::Rc::clone(&counter));
+        }
+        array_deque.remove(1);
+        array_deque.remove(7);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 9);
+        drop(array_deque);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn array_deque_adds_in_front_half() {
+        let mut array_deque = ArrayDeque::new();
+        for i in 0..8 {
+            array_deque.add(i, i);
+        }
+        array_deque.add(2, 100);
+        // deque: [0, 1, 100, 2, 3, 4, 5, 6, 7]
+        assert_eq!(array_deque.get(1), Some(&1));
+        assert_eq!(array_deque.get(2), Some(&100));
+        assert_eq!(array_deque.get(3), Some(&2));
+        assert_eq!(array_deque.remove(1), Some(1));
+        assert_eq!(array_deque.get(0), Some(&0));
+        assert_eq!(array_deque.get(1), Some(&100));
+    }
+
+    #[test]
+    fn array_deque_iterators_work() {
+        let mut array_deque = ArrayDeque::new();
+        for i in 0..6 {
+            array_deque.add(i, i);
+        }
+        array_deque.remove(0);
+        array_deque.remove(0);
+        array_deque.add(4, 6);
+        array_deque.add(5, 7);
+        array_deque.add(6, 8);
+        // deque: [2, 3, 4, 5, 6, 7, 8], wrapped around the end of `a`
+        assert!(array_deque.j + array_deque.n > array_deque.a.len());
+        assert_eq!(array_deque.iter().len(), 7);
+        assert_eq!(
+            array_deque.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 4, 5, 6, 7, 8]
+        );
+        assert_eq!(
+            array_deque.iter().rev().copied().collect::<Vec<_>>(),
+            [8, 7, 6, 5, 4, 3, 2]
+        );
+        for x in &mut array_deque {
+            *x *= 10;
+        }
+        let mut into_iter = array_deque.into_iter();
+        assert_eq!(into_iter.next(), Some(20));
+        assert_eq!(into_iter.next_back(), Some(80));
+        assert_eq!(into_iter.collect::<Vec<_>>(), [30, 40, 50, 60, 70]);
+
+        let mut array_deque: ArrayDeque<_> = (0..3).collect();
+        array_deque.extend(3..5);
+        assert_eq!(array_deque.into_iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn array_deque_capacity_works() {
+        let mut array_deque = ArrayDeque::with_capacity(4);
+        for i in 0..4 {
+            array_deque.add(i, i);
+        }
+        array_deque.remove(0);
+        array_deque.remove(0);
+        array_deque.add(2, 4);
+        array_deque.add(3, 5);
+        // deque: [2, 3, 4, 5], wrapped around
+        assert_eq!(array_deque.capacity(), 4);
+        array_deque.reserve(3);
+        assert_eq!(array_deque.capacity(), 8);
+        assert_eq!(
+            array_deque.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 4, 5]
+        );
+        array_deque.set_growth_policy(GrowthPolicy::never_shrink(2));
+        array_deque.remove(3);
+        array_deque.remove(0);
+        array_deque.remove(0);
+        assert_eq!(array_deque.capacity(), 8);
+        array_deque.shrink_to_fit();
+        assert_eq!(array_deque.capacity(), 1);
+        assert_eq!(array_deque.get(0), Some(&4));
+    }
+
+    #[test]
+    fn array_deque_bulk_operations_work() {
+        let mut array_deque = ArrayDeque::new();
+        array_deque.insert_many(0, 0..8);
+        array_deque.insert_many(2, 10..13);
+        array_deque.insert_many(9, 20..22);
+        let expected = [0, 1, 10, 11, 12, 2, 3, 4, 5, 20, 21, 6, 7];
+        assert_eq!(array_deque.iter().copied().collect::<Vec<_>>(), expected);
+        assert_eq!(array_deque.drain(1..3).collect::<Vec<_>>(), [1, 10]);
+        assert_eq!(array_deque.drain(6..9).collect::<Vec<_>>(), [5, 20, 21]);
+        assert_eq!(
+            array_deque.iter().copied().collect::<Vec<_>>(),
+            [0, 11, 12, 2, 3, 4, 6, 7]
+        );
+
+        let counter = std::rc::Rc::new(());
+        let mut array_deque = (0..6)
+            .map(|_| std::rc::Rc::clone(&counter))
+            .collect::<ArrayDeque<_>>();
+        array_deque.remove_range(1..3);
+        let drain = array_deque.drain(2..);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 5);
+        drop(drain);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 3);
+        drop(array_deque);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn array_deque_end_operations_work() {
+        let mut array_deque = ArrayDeque::new();
+        assert_eq!(array_deque.pop_front(), None);
+        assert_eq!(array_deque.back(), None);
+        for i in 0..4 {
+            array_deque.push_back(i);
+            array_deque.push_front(-i - 1);
+        }
+        // deque: [-4, -3, -2, -1, 0, 1, 2, 3]
+        assert_eq!(array_deque.front(), Some(&-4));
+        assert_eq!(array_deque.back(), Some(&3));
+        *array_deque.front_mut().unwrap() = -5;
+        *array_deque.back_mut().unwrap() = 4;
+        *array_deque.get_mut(4).unwrap() = 10;
+        assert_eq!(array_deque.pop_front(), Some(-5));
+        assert_eq!(array_deque.pop_back(), Some(4));
+        // deque: [-3, -2, -1, 10, 1, 2]
+        let (head, tail) = array_deque.as_slices();
+        assert_eq!([head, tail].concat(), [-3, -2, -1, 10, 1, 2]);
+        array_deque.swap(0, 5);
+        assert_eq!(array_deque.make_contiguous(), [2, -2, -1, 10, 1, -3]);
+        assert_eq!(array_deque.as_slices().1, []);
+        array_deque.clear();
+        assert_eq!(array_deque.size(), 0);
+        array_deque.push_back(1);
+        assert_eq!(array_deque.iter().collect::<Vec<_>>(), [&1]);
+    }
+
+    #[test]
+    fn array_deque_rotates() {
+        for capacity in 7..10 {
+            for k in 0..=7 {
+                // start from a wrapped-around layout
+                let mut array_deque = ArrayDeque::with_capacity(capacity);
+                for i in (0..3).rev() {
+                    array_deque.push_front(i);
+                }
+                for i in 3..7 {
+                    array_deque.push_back(i);
+                }
+                let mut expected = (0..7).collect::<Vec<_>>();
+                array_deque.rotate_left(k);
+                expected.rotate_left(k);
+                assert_eq!(array_deque.iter().copied().collect::<Vec<_>>(), expected);
+                array_deque.rotate_right(k % 4);
+                expected.rotate_right(k % 4);
+                assert_eq!(array_deque.iter().copied().collect::<Vec<_>>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn array_deque_compares_by_contents() {
+        let mut array_deque = ArrayDeque::with_capacity(4);
+        array_deque.push_back(2);
+        array_deque.push_front(1);
+        let other = ArrayDeque::from(vec![1, 2]);
+        assert_eq!(array_deque, other);
+        assert_eq!(format!("{:?}", array_deque), "[1, 2]");
+        assert_eq!(
+            format!("{:#?}", array_deque)
+                .split_whitespace()
+                .collect::<String>(),
+            "ArrayDeque{a:[Some(2,),None,None,Some(1,),],j:3,n:2,}"
+        );
+        assert_eq!(array_deque.clone().capacity(), 4);
+    }
 }
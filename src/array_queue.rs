@@ -1,24 +1,78 @@
-#[derive(Debug)]
+use crate::array_deque::{allocate, as_mut_slices, as_slices, Slots};
+use crate::{
+    error::Error, format, growth_policy::GrowthPolicy, interfaces::Queue, memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+};
+
 pub struct ArrayQueue<T> {
-    a: Box<[T]>,
+    a: Box<[MaybeUninit<T>]>,
     j: usize,
     n: usize,
+    policy: GrowthPolicy,
 }
 
-impl<T: Default> ArrayQueue<T> {
+impl<T> ArrayQueue<T> {
     pub fn new() -> Self {
+        Self::with_capacity(1)
+    }
+
+    /// Creates an empty queue whose backing array has room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            a: Box::new([Default::default(); 1]),
+            a: allocate(std::cmp::max(1, capacity)),
             j: 0,
             n: 0,
+            policy: GrowthPolicy::default(),
         }
     }
 
-    pub fn add(&mut self, x: T) -> bool {
-        if self.n + 1 > self.a.len() {
-            self.resize();
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.policy
+    }
+
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.policy = policy;
+    }
+
+    /// Grows the backing array, if needed, so that `additional` more elements
+    /// fit without resizing.
+    ///
+    /// Panics if the new capacity overflows; see [`try_reserve`](Self::try_reserve).
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(e) = self.try_reserve(additional) {
+            panic!("{}", e);
         }
-        self.a[(self.j + self.n) % self.a.len()] = x;
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+        let required = self
+            .n
+            .checked_add(additional)
+            .ok_or(Error::CapacityExceeded)?;
+        if required > self.a.len() {
+            let capacity = self.policy.grow::<MaybeUninit<T>>(self.a.len(), required)?;
+            self.resize(capacity);
+        }
+        Ok(())
+    }
+
+    /// Shrinks the backing array to hold exactly `size()` elements (at least one).
+    pub fn shrink_to_fit(&mut self) {
+        self.resize(std::cmp::max(1, self.n));
+    }
+
+    pub fn add(&mut self, x: T) -> bool {
+        self.reserve(1);
+        self.a[(self.j + self.n) % self.a.len()] = MaybeUninit::new(x);
         self.n += 1;
 
         true
@@ -28,29 +82,250 @@ impl<T: Default> ArrayQueue<T> {
         if self.n == 0 {
             return None;
         }
-        let x = std::mem::replace(&mut self.a[self.j], Default::default());
+        // SAFETY: slot `j` holds the front element, and advancing `j` below
+        // marks it uninitialized again.
+        let x = unsafe { self.a[self.j].assume_init_read() };
         self.j = (self.j + 1) % self.a.len();
         self.n -= 1;
-        if self.a.len() >= 3 * self.n {
-            self.resize();
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
         }
         Some(x)
     }
 
-    fn resize(&mut self) {
-        let new_a = std::iter::repeat_with(|| Default::default())
-            .take(std::cmp::max(1, 2 * self.n))
-            .collect::<Vec<_>>()
-            .into_boxed_slice();
-        let mut old_a = std::mem::replace(&mut self.a, new_a);
-        old_a.rotate_left(self.j);
-        for (i, ai) in old_a.into_vec().into_iter().take(self.n).enumerate() {
-            self.a[i] = ai;
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = as_slices(&self.a, self.j, self.n);
+        Iter::new(head, tail)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = as_mut_slices(&mut self.a, self.j, self.n);
+        IterMut::new(head, tail)
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        let mut old_a = std::mem::replace(&mut self.a, allocate(capacity));
+        for k in 0..self.n {
+            self.a[k] = std::mem::replace(
+                &mut old_a[(self.j + k) % old_a.len()],
+                MaybeUninit::uninit(),
+            );
         }
         self.j = 0;
     }
 }
 
+impl<T> Drop for ArrayQueue<T> {
+    fn drop(&mut self) {
+        for k in 0..self.n {
+            // SAFETY: the `n` slots starting at `j` are initialized.
+            unsafe { self.a[(self.j + k) % self.a.len()].assume_init_drop() }
+        }
+    }
+}
+
+impl<T> Default for ArrayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for ArrayQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("ArrayQueue")
+                .field("a", &Slots::new(&self.a, self.j, self.n))
+                .field("j", &self.j)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: Clone> Clone for ArrayQueue<T> {
+    fn clone(&self) -> Self {
+        let mut array_queue = Self::with_capacity(self.a.len());
+        array_queue.set_growth_policy(self.policy);
+        array_queue.extend(self.iter().cloned());
+        array_queue
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayQueue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayQueue<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayQueue<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayQueue<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayQueue<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArrayQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayQueue<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<ArrayQueue<T>> for Vec<T> {
+    fn from(xs: ArrayQueue<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> Queue<T> for ArrayQueue<T> {
+    fn push_back(&mut self, x: T) {
+        ArrayQueue::add(self, x);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        ArrayQueue::remove(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        if self.n == 0 {
+            return None;
+        }
+        // SAFETY: slot `j` holds the front element when `n > 0`.
+        Some(unsafe { self.a[self.j].assume_init_ref() })
+    }
+}
+
+impl<T> MemoryUsage for ArrayQueue<T> {
+    fn heap_bytes(&self) -> usize {
+        self.a.len() * std::mem::size_of::<MaybeUninit<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.a.len()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.a.len() - self.n
+    }
+}
+
+pub use crate::array_deque::{Iter, IterMut};
+
+pub struct IntoIter<T> {
+    queue: ArrayQueue<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let queue = &mut self.queue;
+        if queue.n == 0 {
+            return None;
+        }
+        // SAFETY: slot `j` holds the front element, and advancing `j` below
+        // marks it uninitialized again.
+        let x = unsafe { queue.a[queue.j].assume_init_read() };
+        queue.j += 1;
+        if queue.j == queue.a.len() {
+            queue.j = 0;
+        }
+        queue.n -= 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.n, Some(self.queue.n))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let queue = &mut self.queue;
+        if queue.n == 0 {
+            return None;
+        }
+        let mut k = queue.j + queue.n - 1;
+        if k >= queue.a.len() {
+            k -= queue.a.len();
+        }
+        // SAFETY: slot `k` holds the back element, and decrementing `n` below
+        // marks it uninitialized again.
+        let x = unsafe { queue.a[k].assume_init_read() };
+        queue.n -= 1;
+        Some(x)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for ArrayQueue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayQueue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayQueue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for ArrayQueue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for ArrayQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array_queue = Self::new();
+        array_queue.extend(iter);
+        array_queue
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -86,4 +361,90 @@ mod test {
         // queue: [7]
         assert_eq!(array_queue.remove(), Some(7));
     }
+
+    #[test]
+    fn array_queue_moves_and_drops_values() {
+        let counter = std::rc::Rc::new(());
+        let mut array_queue = ArrayQueue::new();
+        for _ in 0..10 {
+            array_queue.add(std::rc::Rc::clone(&counter));
+        }
+        for _ in 0..3 {
+            array_queue.remove();
+        }
+        assert_eq!(std::rc::Rc::strong_count(&counter), 8);
+        drop(array_queue);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn array_queue_iterators_work() {
+        let mut array_queue: ArrayQueue<_> = (0..4).collect();
+        array_queue.remove();
+        array_queue.extend(4..6);
+        // queue: [1, 2, 3, 4, 5]
+        assert_eq!(array_queue.iter().len(), 5);
+        assert_eq!(
+            array_queue.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            array_queue.iter().rev().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 1]
+        );
+        for x in &mut array_queue {
+            *x += 1;
+        }
+        assert_eq!(
+            array_queue.clone().into_iter().rev().collect::<Vec<_>>(),
+            [6, 5, 4, 3, 2]
+        );
+        let mut into_iter = array_queue.clone().into_iter();
+        assert_eq!(into_iter.next_back(), Some(6));
+        assert_eq!(into_iter.next(), Some(2));
+        assert_eq!(into_iter.len(), 3);
+        assert_eq!(array_queue.into_iter().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn array_queue_capacity_works() {
+        let mut array_queue = ArrayQueue::with_capacity(4);
+        for i in 0..4 {
+            array_queue.add(i);
+        }
+        array_queue.remove();
+        array_queue.remove();
+        array_queue.add(4);
+        array_queue.add(5);
+        // queue: [2, 3, 4, 5], wrapped around
+        assert_eq!(array_queue.capacity(), 4);
+        array_queue.set_growth_policy(GrowthPolicy::new(4, 8));
+        array_queue.add(6);
+        assert_eq!(array_queue.capacity(), 16);
+        assert_eq!(
+            array_queue.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 4, 5, 6]
+        );
+        array_queue.remove();
+        array_queue.remove();
+        array_queue.remove();
+        // shrinks to 4n once a.len() >= 8n
+        assert_eq!(array_queue.capacity(), 8);
+        array_queue.shrink_to_fit();
+        assert_eq!(array_queue.capacity(), 2);
+        assert_eq!(array_queue.remove(), Some(5));
+        assert_eq!(array_queue.remove(), Some(6));
+    }
+
+    #[test]
+    fn array_queue_compares_by_contents() {
+        let mut array_queue = ArrayQueue::from(vec![0, 1, 2]);
+        array_queue.remove();
+        array_queue.add(3);
+        let other = (1..4).collect::<ArrayQueue<_>>();
+        assert_eq!(array_queue, other);
+        assert!(array_queue < ArrayQueue::from(vec![1, 2, 4]));
+        assert_eq!(array_queue.clone().to_string(), "[1, 2, 3]");
+        assert_eq!(Vec::from(array_queue), [1, 2, 3]);
+    }
 }
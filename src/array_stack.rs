@@ -1,17 +1,83 @@
-#[derive(Debug)]
+use crate::{
+    error::Error,
+    format,
+    growth_policy::GrowthPolicy,
+    interfaces::{List, Stack},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Index, IndexMut, Range, RangeBounds},
+};
+
+#[derive(Clone)]
 pub struct ArrayStack<T> {
     a: Box<[Option<T>]>,
     n: usize,
+    policy: GrowthPolicy,
 }
 
 impl<T> ArrayStack<T> {
     pub fn new() -> Self {
+        Self::with_capacity(1)
+    }
+
+    /// Creates an empty stack whose backing array has room for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            a: Box::new([None; 1]),
+            a: allocate(std::cmp::max(1, capacity)),
             n: 0,
+            policy: GrowthPolicy::default(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn growth_policy(&self) -> GrowthPolicy {
+        self.policy
+    }
+
+    pub fn set_growth_policy(&mut self, policy: GrowthPolicy) {
+        self.policy = policy;
+    }
+
+    /// Grows the backing array, if needed, so that `additional` more elements
+    /// fit without resizing.
+    ///
+    /// Panics if the new capacity overflows; see [`try_reserve`](Self::try_reserve).
+    pub fn reserve(&mut self, additional: usize) {
+        if let Err(e) = self.try_reserve(additional) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+        let required = self
+            .n
+            .checked_add(additional)
+            .ok_or(Error::CapacityExceeded)?;
+        if required > self.a.len() {
+            let capacity = self.policy.grow::<Option<T>>(self.a.len(), required)?;
+            self.resize(capacity);
         }
+        Ok(())
+    }
+
+    /// The number of slots in the backing array that hold no element.
+    pub fn wasted_space(&self) -> usize {
+        self.a.len() - self.n
     }
 
+    /// Shrinks the backing array to hold exactly `size()` elements (at least one).
+    pub fn shrink_to_fit(&mut self) {
+        self.resize(std::cmp::max(1, self.n));
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
     pub fn get(&self, i: usize) -> Option<&T> {
         if i < self.n {
             self.a[i].as_ref()
@@ -28,46 +94,113 @@ impl<T> ArrayStack<T> {
         }
     }
 
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
     pub fn set(&mut self, i: usize, x: T) -> Option<T> {
-        if i < self.n {
-            self.a[i].replace(x)
-        } else {
-            None
-        }
+        self.try_set(i, x).ok()
     }
 
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(self.a[i].replace(x).unwrap())
+    }
+
+    /// Inserts `x` at index `i`, shifting later elements up.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
     pub fn add(&mut self, i: usize, x: T) {
-        if self.n == self.a.len() {
-            self.resize();
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
         }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        self.try_reserve(1)?;
 
         self.a[i..self.n + 1].rotate_right(1);
         self.a[i] = Some(x);
         self.n += 1;
+        Ok(())
     }
 
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
     pub fn remove(&mut self, i: usize) -> Option<T> {
-        if self.n == 0 {
-            return None;
-        }
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
         let x = self.a[i].take();
         self.a[i..self.n].rotate_left(1);
         self.n -= 1;
-        if self.a.len() >= 3 * self.n {
-            self.resize();
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
+        }
+        Ok(x.unwrap())
+    }
+
+    /// Inserts the elements of `iter` at index `i`, resizing at most once and
+    /// shifting each later element once.
+    ///
+    /// Panics if `i > size()`.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, i: usize, iter: I) {
+        if let Err(e) = Error::check_insert(i, self.n) {
+            panic!("{}", e);
+        }
+        let xs = iter.into_iter().collect::<Vec<_>>();
+        let k = xs.len();
+        self.reserve(k);
+        self.a[i..self.n + k].rotate_right(k);
+        for (y, x) in self.a[i..i + k].iter_mut().zip(xs) {
+            *y = Some(x);
+        }
+        self.n += k;
+    }
+
+    /// Removes the elements in `range`, shifting each later element once.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range);
+    }
+
+    /// Removes the elements in `range` and returns them in order.
+    ///
+    /// The elements are removed immediately, even if the iterator is not consumed.
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let Range { start, end } =
+            Error::check_range(range, self.n).unwrap_or_else(|e| panic!("{}", e));
+        let xs = self.a[start..end]
+            .iter_mut()
+            .map(|x| x.take().unwrap())
+            .collect::<Vec<_>>();
+        self.a[start..self.n].rotate_left(end - start);
+        self.n -= end - start;
+        if let Some(capacity) = self.policy.shrink(self.a.len(), self.n) {
+            self.resize(capacity);
         }
-        x
+        Drain::new(xs)
     }
 
     pub fn size(&self) -> usize {
         self.n
     }
 
-    fn resize(&mut self) {
-        let mut new_a = std::iter::repeat_with(|| None)
-            .take(std::cmp::max(1, 2 * self.n))
-            .collect::<Vec<_>>()
-            .into_boxed_slice();
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.a[..self.n].iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            inner: self.a[..self.n].iter_mut(),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        let mut new_a = allocate(capacity);
         for i in 0..self.n {
             new_a[i] = self.a[i].take();
         }
@@ -75,6 +208,312 @@ impl<T> ArrayStack<T> {
     }
 }
 
+/// Allocates `len` empty slots.
+pub(crate) fn allocate<T>(len: usize) -> Box<[Option<T>]> {
+    std::iter::repeat_with(|| None).take(len).collect()
+}
+
+impl<T> Default for ArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for ArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("ArrayStack")
+                .field("a", &self.a)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for ArrayStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ArrayStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for ArrayStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for ArrayStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for ArrayStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for ArrayStack<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for ArrayStack<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for ArrayStack<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<ArrayStack<T>> for Vec<T> {
+    fn from(xs: ArrayStack<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for ArrayStack<T> {
+    fn size(&self) -> usize {
+        ArrayStack::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        ArrayStack::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        ArrayStack::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        ArrayStack::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        ArrayStack::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        ArrayStack::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        ArrayStack::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        ArrayStack::try_remove(self, i)
+    }
+}
+
+impl<T> Stack<T> for ArrayStack<T> {
+    fn push(&mut self, x: T) {
+        ArrayStack::add(self, self.n, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayStack::remove(self, self.n.checked_sub(1)?)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        ArrayStack::get(self, self.n.checked_sub(1)?)
+    }
+}
+
+impl<T> MemoryUsage for ArrayStack<T> {
+    fn heap_bytes(&self) -> usize {
+        self.a.len() * std::mem::size_of::<Option<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.a.len()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.a.len() - self.n
+    }
+}
+
+pub struct Iter<'a, T> {
+    inner: std::slice::Iter<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x.as_ref().unwrap())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.as_ref().unwrap())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    inner: std::slice::IterMut<'a, Option<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|x| x.as_mut().unwrap())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|x| x.as_mut().unwrap())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    inner: std::vec::IntoIter<Option<T>>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Option::unwrap)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(Option::unwrap)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// The elements removed by a `drain` call, in order.
+pub struct Drain<T> {
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T> Drain<T> {
+    pub(crate) fn new(xs: Vec<T>) -> Self {
+        Self {
+            inner: xs.into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for Drain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<T> {}
+
+impl<T> IntoIterator for ArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut a = self.a.into_vec();
+        a.truncate(self.n);
+        IntoIter {
+            inner: a.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut ArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for ArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_many(self.n, iter);
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for ArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array_stack = Self::new();
+        array_stack.extend(iter);
+        array_stack
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -139,4 +578,86 @@ mod test {
         // a:[7]
         assert_eq!(array_stack.get(0), Some(&7));
     }
+
+    #[test]
+    fn array_stack_iterators_work() {
+        let mut array_stack: ArrayStack<_> = (0..5).collect();
+        assert_eq!(array_stack.iter().len(), 5);
+        assert_eq!(
+            array_stack.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            array_stack.iter().rev().copied().collect::<Vec<_>>(),
+            [4, 3, 2, 1, 0]
+        );
+        for x in &mut array_stack {
+            *x *= 10;
+        }
+        array_stack.extend(vec![50, 60]);
+        assert_eq!(
+            array_stack.into_iter().collect::<Vec<_>>(),
+            [0, 10, 20, 30, 40, 50, 60]
+        );
+    }
+
+    #[test]
+    fn array_stack_capacity_works() {
+        let mut array_stack = ArrayStack::with_capacity(10);
+        for i in 0..10 {
+            array_stack.push(i);
+        }
+        assert_eq!(array_stack.capacity(), 10);
+        array_stack.push(10);
+        assert_eq!(array_stack.capacity(), 20);
+        while array_stack.size() > 6 {
+            array_stack.pop();
+        }
+        // shrinks to 2n once a.len() >= 3n
+        assert_eq!(array_stack.capacity(), 12);
+        array_stack.shrink_to_fit();
+        assert_eq!(array_stack.capacity(), 6);
+        array_stack.reserve(10);
+        assert_eq!(array_stack.capacity(), 16);
+        assert_eq!(
+            array_stack.try_reserve(usize::MAX),
+            Err(Error::CapacityExceeded)
+        );
+
+        array_stack.set_growth_policy(GrowthPolicy::never_shrink(2));
+        while array_stack.pop().is_some() {}
+        assert_eq!(array_stack.capacity(), 16);
+        array_stack.shrink_to_fit();
+        assert_eq!(array_stack.capacity(), 1);
+        assert_eq!(ArrayStack::<i32>::with_capacity(0).capacity(), 1);
+    }
+
+    #[test]
+    fn array_stack_bulk_operations_work() {
+        let mut array_stack = (0..4).collect::<ArrayStack<_>>();
+        array_stack.insert_many(2, 10..15);
+        assert_eq!(
+            array_stack.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 10, 11, 12, 13, 14, 2, 3]
+        );
+        assert_eq!(array_stack.drain(1..4).collect::<Vec<_>>(), [1, 10, 11]);
+        array_stack.remove_range(3..=4);
+        assert_eq!(
+            array_stack.iter().copied().collect::<Vec<_>>(),
+            [0, 12, 13, 3]
+        );
+        array_stack.insert_many(4, std::iter::empty());
+        assert_eq!(
+            array_stack.drain(..).rev().collect::<Vec<_>>(),
+            [3, 13, 12, 0]
+        );
+        assert_eq!(array_stack.size(), 0);
+        assert_eq!(array_stack.capacity(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 4 but the index is 5")]
+    fn array_stack_drain_checks_range() {
+        (0..4).collect::<ArrayStack<_>>().drain(2..5);
+    }
 }
@@ -0,0 +1,178 @@
+use crate::{
+    array_deque::{self, Slots},
+    error::Error,
+};
+use std::{fmt, mem::MaybeUninit};
+
+/// A fixed-capacity circular buffer: the ArrayDeque layout without resizing,
+/// used as the block type of block-based lists. It shares ArrayDeque's slot
+/// helpers, so only the `n` slots starting at `j` are initialized.
+pub(crate) struct BoundedDeque<T> {
+    a: Box<[MaybeUninit<T>]>,
+    j: usize,
+    n: usize,
+}
+
+impl<T> BoundedDeque<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            a: array_deque::allocate(capacity),
+            j: 0,
+            n: 0,
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.n
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.n == self.a.len()
+    }
+
+    pub(crate) fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the `n` slots starting at `j` are initialized.
+        Some(unsafe { self.a[(self.j + i) % self.a.len()].assume_init_ref() })
+    }
+
+    pub(crate) fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the `n` slots starting at `j` are initialized.
+        Some(unsafe { self.a[(self.j + i) % self.a.len()].assume_init_mut() })
+    }
+
+    /// Inserts `x` at index `i`, shifting whichever side of `i` is shorter.
+    ///
+    /// Panics if `i > size()` or the deque is full.
+    pub(crate) fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = Error::check_insert(i, self.n) {
+            panic!("{}", e);
+        }
+        if self.is_full() {
+            panic!("{}", Error::CapacityExceeded);
+        }
+        self.j = array_deque::open_gap(&mut self.a, self.j, self.n, i, 1);
+        self.a[(self.j + i) % self.a.len()] = MaybeUninit::new(x);
+        self.n += 1;
+    }
+
+    /// Removes the element at index `i`, shifting whichever side of `i` is shorter.
+    pub(crate) fn remove(&mut self, i: usize) -> Option<T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: slot `j + i` is initialized; closing the gap below moves
+        // the hole it leaves behind out of the `n - 1` remaining slots.
+        let x = unsafe { self.a[(self.j + i) % self.a.len()].assume_init_read() };
+        self.j = array_deque::close_gap(&mut self.a, self.j, self.n, i, i + 1);
+        self.n -= 1;
+        Some(x)
+    }
+
+    pub(crate) fn push_back(&mut self, x: T) {
+        self.add(self.n, x);
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.n.checked_sub(1)?)
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = array_deque::as_slices(&self.a, self.j, self.n);
+        head.iter().chain(tail.iter())
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = array_deque::as_mut_slices(&mut self.a, self.j, self.n);
+        head.iter_mut().chain(tail.iter_mut())
+    }
+
+    /// The elements, in order.
+    pub(crate) fn into_vec(mut self) -> Vec<T> {
+        let mut xs = Vec::with_capacity(self.n);
+        while let Some(x) = self.pop_front() {
+            xs.push(x);
+        }
+        xs
+    }
+}
+
+impl<T> Drop for BoundedDeque<T> {
+    fn drop(&mut self) {
+        for k in 0..self.n {
+            // SAFETY: the `n` slots starting at `j` are initialized.
+            unsafe { self.a[(self.j + k) % self.a.len()].assume_init_drop() }
+        }
+    }
+}
+
+impl<T: Clone> Clone for BoundedDeque<T> {
+    fn clone(&self) -> Self {
+        let mut bounded_deque = Self::new(self.a.len());
+        for x in self.iter() {
+            bounded_deque.push_back(x.clone());
+        }
+        bounded_deque
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BoundedDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedDeque")
+            .field("a", &Slots::new(&self.a, self.j, self.n))
+            .field("j", &self.j)
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+/// The elements of a block, in order.
+pub(crate) type Iter<'a, T> = std::iter::Chain<std::slice::Iter<'a, T>, std::slice::Iter<'a, T>>;
+
+pub(crate) type IterMut<'a, T> =
+    std::iter::Chain<std::slice::IterMut<'a, T>, std::slice::IterMut<'a, T>>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn bounded_deque_works() {
+        let mut bounded_deque = BoundedDeque::new(4);
+        bounded_deque.push_back(2);
+        bounded_deque.add(0, 1);
+        bounded_deque.add(0, 0);
+        bounded_deque.add(3, 3);
+        // deque: [0, 1, 2, 3], wrapped around
+        assert!(bounded_deque.is_full());
+        assert_eq!(bounded_deque.iter().collect::<Vec<_>>(), [&0, &1, &2, &3]);
+        assert_eq!(bounded_deque.remove(1), Some(1));
+        assert_eq!(bounded_deque.pop_back(), Some(3));
+        *bounded_deque.get_mut(1).unwrap() = 5;
+        assert_eq!(bounded_deque.get(1), Some(&5));
+        assert_eq!(bounded_deque.clone().into_vec(), [0, 5]);
+        assert_eq!(
+            format!("{:?}", bounded_deque)
+                .split_whitespace()
+                .collect::<String>(),
+            "BoundedDeque{a:[Some(0),Some(5),None,None],j:0,n:2}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity exceeded")]
+    fn bounded_deque_rejects_add_when_full() {
+        let mut bounded_deque = BoundedDeque::new(2);
+        bounded_deque.push_back(0);
+        bounded_deque.push_back(1);
+        bounded_deque.add(1, 2);
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{
+    array_stack::ArrayStack, format, hashing::Multiplicative, interfaces::USet, memory::MemoryUsage,
+};
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::Flatten,
+};
+
+/// A hash table that keeps the elements hashing to each of its 2^d slots
+/// in an ArrayStack. Slots are chosen by the hash codes of `S`, multiplicative
+/// hashing with a random odd `z` by default, and the table is rebuilt to keep
+/// `n <= t.len() < 3n`, so the expected bucket size is O(1).
+#[derive(Clone)]
+pub struct ChainedHashTable<T, S = Multiplicative> {
+    t: Box<[ArrayStack<T>]>,
+    d: u32,
+    n: usize,
+    hasher: S,
+}
+
+impl<T: Hash + Eq> ChainedHashTable<T> {
+    /// Creates an empty table whose multiplier is drawn from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_hasher(Multiplicative::new())
+    }
+
+    /// Creates an empty table whose multiplier is drawn from a
+    /// generator seeded with `seed`, so the same operations fill the same slots.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(Multiplicative::with_seed(seed))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> ChainedHashTable<T, S> {
+    /// Creates an empty table whose slots come from the top bits of the
+    /// hash codes that `hasher` builds, so those bits should be well mixed.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            t: allocate(2),
+            d: 1,
+            n: 0,
+            hasher,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.t.iter().flatten(),
+            len: self.n,
+        }
+    }
+
+    /// The slot for `x`: the top `d` bits of its hash code.
+    fn hash(&self, x: &T) -> usize {
+        (self.hasher.hash_one(x) >> (64 - self.d)) as usize
+    }
+
+    /// Returns the element equal to `x`, or `None` if there is none.
+    pub fn find(&self, x: &T) -> Option<&T> {
+        self.t[self.hash(x)].iter().find(|y| *y == x)
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.find(x).is_some()
+    }
+
+    /// Adds `x` unless an equal element is present; returns whether it was added.
+    pub fn add(&mut self, x: T) -> bool {
+        if self.find(&x).is_some() {
+            return false;
+        }
+        if self.n + 1 > self.t.len() {
+            self.resize(self.n + 1);
+        }
+        let bucket = &mut self.t[self.hash(&x)];
+        bucket.add(bucket.size(), x);
+        self.n += 1;
+        true
+    }
+
+    /// Removes and returns the element equal to `x`, or `None` if there is none.
+    pub fn remove(&mut self, x: &T) -> Option<T> {
+        let bucket = &mut self.t[self.hash(x)];
+        let j = bucket.iter().position(|y| y == x)?;
+        let y = bucket.remove(j);
+        self.n -= 1;
+        if 3 * self.n < self.t.len() && self.d > 1 {
+            self.resize(self.n);
+        }
+        y
+    }
+
+    pub fn clear(&mut self) {
+        self.t = allocate(2);
+        self.d = 1;
+        self.n = 0;
+    }
+
+    /// Rebuilds the table with the smallest 2^d > `n` slots (at least 2).
+    fn resize(&mut self, n: usize) {
+        self.d = 1;
+        while 1 << self.d <= n {
+            self.d += 1;
+        }
+        let old = std::mem::replace(&mut self.t, allocate(1 << self.d));
+        for x in old.into_vec().into_iter().flatten() {
+            let bucket = &mut self.t[self.hash(&x)];
+            bucket.add(bucket.size(), x);
+        }
+    }
+}
+
+fn allocate<T>(len: usize) -> Box<[ArrayStack<T>]> {
+    std::iter::repeat_with(ArrayStack::new).take(len).collect()
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> Default for ChainedHashTable<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+/// Formats as the set of elements; `{:#?}` shows the buckets instead.
+impl<T: fmt::Debug, S> fmt::Debug for ChainedHashTable<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("ChainedHashTable")
+                .field("t", &self.t)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_set().entries(self.t.iter().flatten()).finish()
+        }
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> PartialEq for ChainedHashTable<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().all(|x| other.contains(x))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> Eq for ChainedHashTable<T, S> {}
+
+impl<T: Hash + Eq + fmt::Display, S: BuildHasher> fmt::Display for ChainedHashTable<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_set(f, self.iter())
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> From<Vec<T>> for ChainedHashTable<T, S> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T, S> From<ChainedHashTable<T, S>> for Vec<T> {
+    fn from(xs: ChainedHashTable<T, S>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> USet<T> for ChainedHashTable<T, S> {
+    fn size(&self) -> usize {
+        ChainedHashTable::size(self)
+    }
+
+    fn add(&mut self, x: T) -> bool {
+        ChainedHashTable::add(self, x)
+    }
+
+    fn remove(&mut self, x: &T) -> Option<T> {
+        ChainedHashTable::remove(self, x)
+    }
+
+    fn find(&self, x: &T) -> Option<&T> {
+        ChainedHashTable::find(self, x)
+    }
+}
+
+impl<T, S> MemoryUsage for ChainedHashTable<T, S> {
+    fn heap_bytes(&self) -> usize {
+        self.t.len() * std::mem::size_of::<ArrayStack<T>>()
+            + self.t.iter().map(|b| b.heap_bytes()).sum::<usize>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.t.iter().map(|b| b.allocated_slots()).sum()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.allocated_slots() - self.n
+    }
+}
+
+/// Yields the elements bucket by bucket, in no particular order.
+pub struct Iter<'a, T> {
+    inner: Flatten<std::slice::Iter<'a, ArrayStack<T>>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.inner.next()?;
+        self.len -= 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IntoIter<T> {
+    inner: Flatten<std::vec::IntoIter<ArrayStack<T>>>,
+    len: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.inner.next()?;
+        self.len -= 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T, S> IntoIterator for ChainedHashTable<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.t.into_vec().into_iter().flatten(),
+            len: self.n,
+        }
+    }
+}
+
+impl<'a, T: Hash + Eq, S: BuildHasher> IntoIterator for &'a ChainedHashTable<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> Extend<T> for ChainedHashTable<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> std::iter::FromIterator<T> for ChainedHashTable<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut chained_hash_table = Self::with_hasher(S::default());
+        chained_hash_table.extend(iter);
+        chained_hash_table
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn chained_hash_table_works() {
+        let mut chained_hash_table = ChainedHashTable::with_seed(5);
+        let mut expected = HashSet::new();
+        for i in 0..1000 {
+            let x = (i * 37) % 601;
+            assert_eq!(chained_hash_table.add(x), expected.insert(x));
+            assert!(chained_hash_table.size() <= chained_hash_table.t.len());
+        }
+        assert_eq!(chained_hash_table.size(), expected.len());
+        assert_eq!(
+            chained_hash_table.iter().collect::<HashSet<_>>(),
+            expected.iter().collect()
+        );
+        for i in 0..700 {
+            let x = (i * 13) % 650;
+            assert_eq!(chained_hash_table.remove(&x), expected.take(&x));
+            let y = (i * 7) % 700;
+            assert_eq!(chained_hash_table.find(&y), expected.get(&y));
+            assert!(chained_hash_table.t.len() <= 3 * chained_hash_table.size() + 2);
+        }
+        assert_eq!(chained_hash_table.size(), expected.len());
+        let mut xs = Vec::from(chained_hash_table);
+        xs.sort();
+        let mut ys = expected.into_iter().collect::<Vec<_>>();
+        ys.sort();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn chained_hash_table_compares_as_a_set() {
+        let a = (0..20).collect::<ChainedHashTable<_>>();
+        let mut b = (0..20).rev().collect::<ChainedHashTable<_>>();
+        assert_eq!(a, b);
+        b.remove(&3);
+        b.add(20);
+        assert_ne!(a, b);
+        let mut c = ChainedHashTable::new();
+        c.add("x");
+        assert_eq!(c.to_string(), "{x}");
+        c.clear();
+        assert_eq!(c.size(), 0);
+        assert_eq!(format!("{:?}", c), "{}");
+    }
+
+    #[test]
+    fn chained_hash_table_takes_any_hasher() {
+        use crate::hashing::Polynomial;
+        use std::collections::hash_map::RandomState;
+        let mut a = ChainedHashTable::with_hasher(Polynomial::with_seed(2));
+        let mut b = ChainedHashTable::with_hasher(RandomState::new());
+        for x in 0..500 {
+            assert!(a.add(x * 7));
+            assert!(b.add(x * 7));
+        }
+        assert!(!a.add(14));
+        assert_eq!(a.find(&21), Some(&21));
+        assert_eq!(b.remove(&21), Some(21));
+        assert_eq!(b.find(&21), None);
+        assert_eq!(a.size(), 500);
+        assert_eq!(b.size(), 499);
+    }
+}
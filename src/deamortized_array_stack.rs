@@ -0,0 +1,536 @@
+use crate::{
+    array_deque::{self, allocate, Slots},
+    error::Error,
+    format,
+    interfaces::{List, Stack},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ops::{Index, IndexMut},
+};
+
+/// An ArrayStack that resizes incrementally: instead of copying all `n`
+/// elements at once, it allocates the new array `b` next to `a` and moves two
+/// elements per `add` or `remove` until `a` is empty. While a resize is in
+/// progress, indices below `k` live in `a` and the rest in `b`.
+pub struct DeamortizedArrayStack<T> {
+    a: Box<[MaybeUninit<T>]>,
+    b: Option<Box<[MaybeUninit<T>]>>,
+    k: usize,
+    n: usize,
+}
+
+impl<T> DeamortizedArrayStack<T> {
+    pub fn new() -> Self {
+        Self {
+            a: allocate(1),
+            b: None,
+            k: 0,
+            n: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        Iter::new(head, tail)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = self.as_mut_slices();
+        IterMut::new(head, tail)
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the slots for indices below `n` are initialized.
+        Some(unsafe { self.slot(i).assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the slots for indices below `n` are initialized.
+        Some(unsafe { self.slot_mut(i).assume_init_mut() })
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+
+    /// Adds `x` at the end in worst-case O(1) time.
+    pub fn push(&mut self, x: T) {
+        if self.b.is_none() && self.n == self.a.len() {
+            self.begin_resize(2 * self.n);
+        }
+        *self.slot_mut(self.n) = MaybeUninit::new(x);
+        self.n += 1;
+        self.migrate();
+    }
+
+    /// Removes the last element in worst-case O(1) time.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        self.n -= 1;
+        // SAFETY: slot `n` held the last element, and decrementing `n` above
+        // marks it uninitialized again.
+        let x = unsafe { self.slot(self.n).assume_init_read() };
+        self.k = std::cmp::min(self.k, self.n);
+        if self.b.is_none() && self.a.len() > 1 && self.a.len() >= 3 * self.n {
+            self.begin_resize(std::cmp::max(1, 2 * self.n));
+        }
+        self.migrate();
+        Some(x)
+    }
+
+    /// Inserts `x` at index `i`, shifting later elements up.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        self.push(x);
+        for m in (i..self.n - 1).rev() {
+            self.swap_slots(m, m + 1);
+        }
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        for m in i..self.n - 1 {
+            self.swap_slots(m, m + 1);
+        }
+        Ok(self.pop().unwrap())
+    }
+
+    /// Whether a resize is still moving elements from the old array.
+    pub fn is_resizing(&self) -> bool {
+        self.b.is_some()
+    }
+
+    fn begin_resize(&mut self, len: usize) {
+        self.b = Some(allocate(len));
+        self.k = self.n;
+    }
+
+    /// Moves up to two elements from `a` into `b`, replacing `a` with `b`
+    /// once every element has moved.
+    fn migrate(&mut self) {
+        let b = match &mut self.b {
+            Some(b) => b,
+            None => return,
+        };
+        for _ in 0..2 {
+            if self.k == 0 {
+                break;
+            }
+            self.k -= 1;
+            b[self.k] = std::mem::replace(&mut self.a[self.k], MaybeUninit::uninit());
+        }
+        if self.k == 0 {
+            self.a = self.b.take().unwrap();
+        }
+    }
+
+    fn slot(&self, i: usize) -> &MaybeUninit<T> {
+        match &self.b {
+            Some(b) if i >= self.k => &b[i],
+            _ => &self.a[i],
+        }
+    }
+
+    fn slot_mut(&mut self, i: usize) -> &mut MaybeUninit<T> {
+        match &mut self.b {
+            Some(b) if i >= self.k => &mut b[i],
+            _ => &mut self.a[i],
+        }
+    }
+
+    fn swap_slots(&mut self, i: usize, j: usize) {
+        let x = std::mem::replace(self.slot_mut(i), MaybeUninit::uninit());
+        let y = std::mem::replace(self.slot_mut(j), x);
+        *self.slot_mut(i) = y;
+    }
+
+    fn raw_slices(&self) -> (&[MaybeUninit<T>], &[MaybeUninit<T>]) {
+        match &self.b {
+            Some(b) => (&self.a[..self.k], &b[self.k..self.n]),
+            None => (&self.a[..self.n], &[]),
+        }
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, tail) = self.raw_slices();
+        // SAFETY: the slots for indices below `n` are initialized.
+        unsafe {
+            (
+                &*(head as *const [MaybeUninit<T>] as *const [T]),
+                &*(tail as *const [MaybeUninit<T>] as *const [T]),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, tail) = match &mut self.b {
+            Some(b) => (&mut self.a[..self.k], &mut b[self.k..self.n]),
+            None => (&mut self.a[..self.n], &mut [][..]),
+        };
+        // SAFETY: the slots for indices below `n` are initialized.
+        unsafe {
+            (
+                &mut *(head as *mut [MaybeUninit<T>] as *mut [T]),
+                &mut *(tail as *mut [MaybeUninit<T>] as *mut [T]),
+            )
+        }
+    }
+}
+
+impl<T> Drop for DeamortizedArrayStack<T> {
+    fn drop(&mut self) {
+        let (head, tail) = self.as_mut_slices();
+        // SAFETY: `head` and `tail` hold the initialized elements, which are
+        // not used again.
+        unsafe {
+            std::ptr::drop_in_place(head);
+            std::ptr::drop_in_place(tail);
+        }
+    }
+}
+
+impl<T> Default for DeamortizedArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for DeamortizedArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let (head, tail) = self.raw_slices();
+            f.debug_struct("DeamortizedArrayStack")
+                .field("a", &Slots::new(&self.a, 0, head.len()))
+                .field(
+                    "b",
+                    &self.b.as_ref().map(|b| Slots::new(b, self.k, tail.len())),
+                )
+                .field("k", &self.k)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: Clone> Clone for DeamortizedArrayStack<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DeamortizedArrayStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DeamortizedArrayStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for DeamortizedArrayStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for DeamortizedArrayStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for DeamortizedArrayStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for DeamortizedArrayStack<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for DeamortizedArrayStack<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DeamortizedArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for DeamortizedArrayStack<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<DeamortizedArrayStack<T>> for Vec<T> {
+    fn from(xs: DeamortizedArrayStack<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for DeamortizedArrayStack<T> {
+    fn size(&self) -> usize {
+        DeamortizedArrayStack::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        DeamortizedArrayStack::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        DeamortizedArrayStack::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        DeamortizedArrayStack::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        DeamortizedArrayStack::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        DeamortizedArrayStack::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        DeamortizedArrayStack::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        DeamortizedArrayStack::try_remove(self, i)
+    }
+}
+
+impl<T> Stack<T> for DeamortizedArrayStack<T> {
+    fn push(&mut self, x: T) {
+        DeamortizedArrayStack::push(self, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        DeamortizedArrayStack::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        DeamortizedArrayStack::get(self, self.n.checked_sub(1)?)
+    }
+}
+
+pub use array_deque::{Iter, IterMut};
+
+impl<T> MemoryUsage for DeamortizedArrayStack<T> {
+    fn heap_bytes(&self) -> usize {
+        self.allocated_slots() * std::mem::size_of::<MaybeUninit<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.a.len() + self.b.as_ref().map_or(0, |b| b.len())
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.allocated_slots() - self.n
+    }
+}
+
+pub struct IntoIter<T> {
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DeamortizedArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut xs = Vec::with_capacity(self.n);
+        let (head, tail) = self.raw_slices();
+        for x in head.iter().chain(tail) {
+            // SAFETY: `head` and `tail` hold the initialized elements, and
+            // `n` is reset below so `drop` does not drop them again.
+            xs.push(unsafe { x.assume_init_read() });
+        }
+        self.k = 0;
+        self.n = 0;
+        IntoIter {
+            inner: xs.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DeamortizedArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DeamortizedArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for DeamortizedArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for DeamortizedArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deamortized_array_stack = Self::new();
+        deamortized_array_stack.extend(iter);
+        deamortized_array_stack
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn deamortized_array_stack_works() {
+        let mut deamortized_array_stack = DeamortizedArrayStack::new();
+        let mut expected = Vec::new();
+        for i in 0..100 {
+            deamortized_array_stack.push(i);
+            expected.push(i);
+            assert_eq!(
+                deamortized_array_stack.iter().copied().collect::<Vec<_>>(),
+                expected
+            );
+        }
+        deamortized_array_stack.add(10, -1);
+        expected.insert(10, -1);
+        assert_eq!(
+            deamortized_array_stack.remove(50),
+            Some(expected.remove(50))
+        );
+        while let Some(x) = deamortized_array_stack.pop() {
+            assert_eq!(Some(x), expected.pop());
+            for (i, y) in expected.iter().enumerate() {
+                assert_eq!(deamortized_array_stack.get(i), Some(y));
+            }
+        }
+        assert_eq!(deamortized_array_stack.size(), 0);
+    }
+
+    #[test]
+    fn deamortized_array_stack_resizes_incrementally() {
+        let mut deamortized_array_stack = (0..8).collect::<DeamortizedArrayStack<_>>();
+        assert!(!deamortized_array_stack.is_resizing());
+        deamortized_array_stack.push(8);
+        // a: [0..8], b: 16 slots holding 6, 7 and 8
+        assert!(deamortized_array_stack.is_resizing());
+        assert_eq!(deamortized_array_stack.get(5), Some(&5));
+        assert_eq!(deamortized_array_stack.get(6), Some(&6));
+        deamortized_array_stack.set(2, 20);
+        assert_eq!(deamortized_array_stack.pop(), Some(8));
+        deamortized_array_stack.push(9);
+        deamortized_array_stack.push(10);
+        assert!(!deamortized_array_stack.is_resizing());
+        assert_eq!(
+            Vec::from(deamortized_array_stack),
+            [0, 1, 20, 3, 4, 5, 6, 7, 9, 10]
+        );
+    }
+
+    #[test]
+    fn deamortized_array_stack_drops_values() {
+        let counter = std::rc::Rc::new(());
+        let mut deamortized_array_stack = DeamortizedArrayStack::new();
+        for _ in 0..9 {
+            deamortized_array_stack.push(std::rc::Rc::clone(&counter));
+        }
+        assert!(deamortized_array_stack.is_resizing());
+        deamortized_array_stack.remove(0);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 9);
+        drop(deamortized_array_stack);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+}
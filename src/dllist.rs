@@ -1,118 +1,866 @@
+use crate::{
+    error::Error,
+    format,
+    interfaces::{Deque, List, Queue},
+    memory::MemoryUsage,
+};
 use std::{
-    cell::RefCell,
-    rc::{Rc, Weak},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Index, IndexMut},
+    ptr::NonNull,
 };
-#[derive(Debug)]
-struct DLList<T> {
+
+pub struct DLList<T> {
     n: usize,
-    head: Link<T>,
-    tail: Link<T>,
+    dummy: Link<T>,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-type Link<T> = Option<Rc<RefCell<Node<T>>>>;
-type WLink<T> = Option<Weak<RefCell<Node<T>>>>;
+type Link<T> = NonNull<Node<T>>;
 
-#[derive(Debug, Default)]
 struct Node<T> {
-    x: T,
+    x: MaybeUninit<T>,
     next: Link<T>,
-    prev: WLink<T>,
+    prev: Link<T>,
 }
 
-impl<T: Default + Clone> DLList<T> {
+unsafe impl<T: Send> Send for DLList<T> {}
+unsafe impl<T: Sync> Sync for DLList<T> {}
+
+impl<T> DLList<T> {
     pub fn new() -> Self {
-        let head = Node::new(Default::default());
-        let tail = Node::new(Default::default());
-        head.borrow_mut().prev = None;
-        head.borrow_mut().next = Some(Rc::clone(&tail));
-        tail.borrow_mut().next = None;
-        tail.borrow_mut().prev = Some(Rc::downgrade(&head));
+        let dummy = Node::new(MaybeUninit::uninit());
+        // SAFETY: `dummy` was just allocated and is not shared yet.
+        unsafe {
+            (*dummy.as_ptr()).next = dummy;
+            (*dummy.as_ptr()).prev = dummy;
+        }
         Self {
             n: 0,
-            head: Some(head),
-            tail: Some(tail),
+            dummy,
+            marker: PhantomData,
         }
     }
-    fn get_node(&self, i: usize) -> Rc<RefCell<Node<T>>> {
+    fn get_node(&self, i: usize) -> Link<T> {
         let mut p;
-        if i < self.n / 2 {
-            p = Rc::clone(self.head.as_ref().unwrap().borrow().next.as_ref().unwrap());
-            for _ in 0..i {
-                let a = Rc::clone(p.borrow().next.as_ref().unwrap());
-                p = a;
+        // SAFETY: every link reachable from `dummy` points to a live node.
+        unsafe {
+            if i < self.n / 2 {
+                p = (*self.dummy.as_ptr()).next;
+                for _ in 0..i {
+                    p = (*p.as_ptr()).next;
+                }
+            } else {
+                p = self.dummy;
+                for _ in 0..(self.n - i) {
+                    p = (*p.as_ptr()).prev;
+                }
             }
-        } else {
-            p = Rc::clone(self.tail.as_ref().unwrap());
-            for _ in 0..(self.n - i) {
-                let a = Rc::clone(&p.borrow().prev.as_ref().unwrap().upgrade().unwrap());
-                p = a;
+        }
+        p
+    }
+    pub fn size(&self) -> usize {
+        self.n
+    }
+    pub fn iter(&self) -> Iter<'_, T> {
+        // SAFETY: `dummy` is always a live node.
+        unsafe {
+            Iter {
+                head: (*self.dummy.as_ptr()).next,
+                tail: (*self.dummy.as_ptr()).prev,
+                len: self.n,
+                marker: PhantomData,
             }
         }
-        Rc::clone(&p)
     }
-    pub fn get(&self, i: usize) -> Option<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        // SAFETY: `dummy` is always a live node.
+        unsafe {
+            IterMut {
+                head: (*self.dummy.as_ptr()).next,
+                tail: (*self.dummy.as_ptr()).prev,
+                len: self.n,
+                marker: PhantomData,
+            }
+        }
+    }
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
         if i >= self.n {
             return None;
         }
-        Some(self.get_node(i).borrow().x.clone())
+        // SAFETY: `get_node(i)` is a live, initialized node for `i < n`.
+        Some(unsafe { (*self.get_node(i).as_ptr()).x.assume_init_ref() })
     }
-    pub fn set(&self, i: usize, x: T) -> Option<T> {
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
         if i >= self.n {
             return None;
         }
-        let u = self.get_node(i);
-        let y = u.borrow().x.clone();
-        u.borrow_mut().x = x;
-        Some(y)
-    }
-    fn add_before(&mut self, w: Rc<RefCell<Node<T>>>, x: T) {
-        let u = Node::new(x);
-        u.borrow_mut().prev = Some(Rc::downgrade(
-            &w.borrow().prev.as_ref().unwrap().upgrade().unwrap(),
-        ));
-        u.borrow_mut().next = Some(Rc::clone(&w));
-        w.borrow_mut().prev = Some(Rc::downgrade(&u));
-        u.borrow()
-            .prev
-            .as_ref()
-            .unwrap()
-            .upgrade()
-            .unwrap()
-            .borrow_mut()
-            .next = Some(Rc::clone(&u));
-        self.n += 1;
+        // SAFETY: `get_node(i)` is a live, initialized node for `i < n`.
+        Some(unsafe { (*self.get_node(i).as_ptr()).x.assume_init_mut() })
     }
-
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+    fn add_before(&mut self, w: Link<T>, x: T) -> Link<T> {
+        // SAFETY: `w` and its predecessor are live nodes of this list.
+        unsafe {
+            let u = Node::new(MaybeUninit::new(x));
+            (*u.as_ptr()).prev = (*w.as_ptr()).prev;
+            (*u.as_ptr()).next = w;
+            (*w.as_ptr()).prev = u;
+            (*(*u.as_ptr()).prev.as_ptr()).next = u;
+            self.n += 1;
+            u
+        }
+    }
+    /// Inserts `x` at index `i`, walking from whichever end is closer.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
     pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
         self.add_before(self.get_node(i), x);
+        Ok(())
+    }
+    fn remove_node(&mut self, w: Link<T>) -> T {
+        // SAFETY: `w` is a live, initialized node of this list other than
+        // `dummy`; once unlinked it is owned here and freed.
+        unsafe {
+            (*(*w.as_ptr()).prev.as_ptr()).next = (*w.as_ptr()).next;
+            (*(*w.as_ptr()).next.as_ptr()).prev = (*w.as_ptr()).prev;
+            self.n -= 1;
+            Box::from_raw(w.as_ptr()).x.assume_init()
+        }
+    }
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        Ok(self.remove_node(self.get_node(i)))
+    }
+    pub fn append(&mut self, other: &mut DLList<T>) {
+        // SAFETY: `dummy` is always a live node.
+        let last = unsafe { (*self.dummy.as_ptr()).prev };
+        self.splice_nodes(last, std::mem::take(other));
+    }
+    pub fn split_off(&mut self, i: usize) -> DLList<T> {
+        assert!(
+            i <= self.n,
+            "split index (is {}) should be <= len (is {})",
+            i,
+            self.n
+        );
+        // SAFETY: `dummy` is always a live node.
+        let last = unsafe { (*self.dummy.as_ptr()).prev };
+        self.split_nodes(self.get_node(i), last, self.n - i)
+    }
+    pub fn splice(&mut self, i: usize, other: DLList<T>) {
+        assert!(
+            i <= self.n,
+            "splice index (is {}) should be <= len (is {})",
+            i,
+            self.n
+        );
+        // SAFETY: `get_node(i)` is a live node of this list.
+        let prev = unsafe { (*self.get_node(i).as_ptr()).prev };
+        self.splice_nodes(prev, other);
+    }
+    pub fn reverse(&mut self) {
+        let mut u = self.dummy;
+        // SAFETY: swapping `next` and `prev` on every node of the ring,
+        // including `dummy`, leaves a consistent ring in reverse order.
+        unsafe {
+            loop {
+                let node = &mut *u.as_ptr();
+                std::mem::swap(&mut node.next, &mut node.prev);
+                u = node.prev;
+                if u == self.dummy {
+                    break;
+                }
+            }
+        }
+    }
+    /// Rotates the list so that the element at index `i` moves to index `(i + r) % n`.
+    pub fn rotate(&mut self, r: usize) {
+        if self.n == 0 || r.is_multiple_of(self.n) {
+            return;
+        }
+        let w = self.get_node(self.n - r % self.n);
+        // SAFETY: `dummy` is unlinked from the ring and relinked before `w`,
+        // another live node of this list.
+        unsafe {
+            let dummy = &mut *self.dummy.as_ptr();
+            (*dummy.prev.as_ptr()).next = dummy.next;
+            (*dummy.next.as_ptr()).prev = dummy.prev;
+            dummy.prev = (*w.as_ptr()).prev;
+            dummy.next = w;
+            (*dummy.prev.as_ptr()).next = self.dummy;
+            (*w.as_ptr()).prev = self.dummy;
+        }
     }
-    fn remove_node(&mut self, w: Rc<RefCell<Node<T>>>) {
-        w.borrow()
-            .prev
-            .as_ref()
-            .unwrap()
-            .upgrade()
-            .unwrap()
-            .borrow_mut()
-            .next = Some(Rc::clone(&w.borrow().next.as_ref().unwrap()));
-        w.borrow().next.as_ref().unwrap().borrow_mut().prev =
-            Some(w.borrow().prev.as_ref().unwrap().clone());
-        self.n -= 1;
+    /// Truncates the list to its first `i` elements and returns the rest.
+    pub fn truncate(&mut self, i: usize) -> DLList<T> {
+        self.split_off(i)
+    }
+    /// Moves every element of `l2` to the end of this list, leaving `l2` empty.
+    pub fn absorb(&mut self, l2: &mut DLList<T>) {
+        self.append(l2);
+    }
+    /// Removes the elements at odd indices and returns them as a new list.
+    pub fn deal(&mut self) -> DLList<T> {
+        let mut other = DLList::new();
+        // SAFETY: every second node is unlinked from this list and relinked
+        // at the end of `other`.
+        unsafe {
+            let mut u = (*self.dummy.as_ptr()).next;
+            while u != self.dummy && (*u.as_ptr()).next != self.dummy {
+                let w = (*u.as_ptr()).next;
+                let next = (*w.as_ptr()).next;
+                (*u.as_ptr()).next = next;
+                (*next.as_ptr()).prev = u;
+                let last = (*other.dummy.as_ptr()).prev;
+                (*w.as_ptr()).prev = last;
+                (*w.as_ptr()).next = other.dummy;
+                (*last.as_ptr()).next = w;
+                (*other.dummy.as_ptr()).prev = w;
+                self.n -= 1;
+                other.n += 1;
+                u = next;
+            }
+        }
+        other
     }
-    pub fn remove(&mut self, i: usize) {
-        if i < self.n {
-            self.remove_node(self.get_node(i));
+    pub fn cursor(&self, i: usize) -> Cursor<'_, T> {
+        let i = std::cmp::min(i, self.n);
+        Cursor {
+            index: i,
+            current: self.get_node(i),
+            list: self,
         }
     }
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        self.cursor(0)
+    }
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        self.cursor(self.n.saturating_sub(1))
+    }
+    pub fn cursor_mut(&mut self, i: usize) -> CursorMut<'_, T> {
+        let i = std::cmp::min(i, self.n);
+        CursorMut {
+            index: i,
+            current: self.get_node(i),
+            list: self,
+        }
+    }
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        self.cursor_mut(0)
+    }
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        self.cursor_mut(self.n.saturating_sub(1))
+    }
+    fn value(&self, u: Link<T>) -> Option<&T> {
+        if u == self.dummy {
+            return None;
+        }
+        // SAFETY: every node of this list other than `dummy` is initialized.
+        Some(unsafe { (*u.as_ptr()).x.assume_init_ref() })
+    }
+    fn value_mut(&mut self, u: Link<T>) -> Option<&mut T> {
+        if u == self.dummy {
+            return None;
+        }
+        // SAFETY: every node of this list other than `dummy` is initialized.
+        Some(unsafe { (*u.as_ptr()).x.assume_init_mut() })
+    }
+    fn split_nodes(&mut self, first: Link<T>, last: Link<T>, len: usize) -> DLList<T> {
+        let mut other = DLList::new();
+        if len == 0 {
+            return other;
+        }
+        // SAFETY: `first..=last` is a run of `len` live nodes of this list;
+        // they are moved, links and all, between `other.dummy`'s links.
+        unsafe {
+            let prev = (*first.as_ptr()).prev;
+            let next = (*last.as_ptr()).next;
+            (*prev.as_ptr()).next = next;
+            (*next.as_ptr()).prev = prev;
+            (*first.as_ptr()).prev = other.dummy;
+            (*last.as_ptr()).next = other.dummy;
+            (*other.dummy.as_ptr()).next = first;
+            (*other.dummy.as_ptr()).prev = last;
+        }
+        self.n -= len;
+        other.n = len;
+        other
+    }
+    fn splice_nodes(&mut self, w: Link<T>, mut other: DLList<T>) {
+        if other.n == 0 {
+            return;
+        }
+        // SAFETY: `w` is a live node of this list and `other` is non-empty;
+        // its nodes are relinked after `w` and `other` is left empty.
+        unsafe {
+            let first = (*other.dummy.as_ptr()).next;
+            let last = (*other.dummy.as_ptr()).prev;
+            let next = (*w.as_ptr()).next;
+            (*w.as_ptr()).next = first;
+            (*first.as_ptr()).prev = w;
+            (*last.as_ptr()).next = next;
+            (*next.as_ptr()).prev = last;
+            (*other.dummy.as_ptr()).next = other.dummy;
+            (*other.dummy.as_ptr()).prev = other.dummy;
+        }
+        self.n += other.n;
+        other.n = 0;
+    }
 }
 
-impl<T: Default> Node<T> {
-    fn new(x: T) -> Rc<RefCell<Node<T>>> {
-        Rc::new(RefCell::new(Self {
+impl<T> Node<T> {
+    fn new(x: MaybeUninit<T>) -> Link<T> {
+        let u = Box::new(Self {
             x,
-            prev: Default::default(),
-            next: Default::default(),
-        }))
+            next: NonNull::dangling(),
+            prev: NonNull::dangling(),
+        });
+        NonNull::from(Box::leak(u))
+    }
+}
+
+impl<T> Drop for DLList<T> {
+    fn drop(&mut self) {
+        while self.n > 0 {
+            // SAFETY: `dummy.next` is an initialized node while `n > 0`.
+            self.remove_node(unsafe { (*self.dummy.as_ptr()).next });
+        }
+        // SAFETY: `dummy` was allocated by `Node::new` and its value is
+        // uninitialized, so freeing it drops no `T`.
+        unsafe { drop(Box::from_raw(self.dummy.as_ptr())) }
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the node chain walked
+/// both ways from the dummy instead.
+impl<T: fmt::Debug> fmt::Debug for DLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let mut next = Vec::new();
+            let mut prev = Vec::new();
+            // SAFETY: every node reachable from `dummy` other than `dummy`
+            // itself is a live element node with an initialized value.
+            unsafe {
+                let mut u = (&*self.dummy.as_ptr()).next;
+                while u != self.dummy {
+                    let node = &*u.as_ptr();
+                    next.push(node.x.assume_init_ref());
+                    u = node.next;
+                }
+                let mut u = (&*self.dummy.as_ptr()).prev;
+                while u != self.dummy {
+                    let node = &*u.as_ptr();
+                    prev.push(node.x.assume_init_ref());
+                    u = node.prev;
+                }
+            }
+            f.debug_struct("DLList")
+                .field("next", &next)
+                .field("prev", &prev)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T> Default for DLList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for DLList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DLList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DLList<T> {}
+
+impl<T: PartialOrd> PartialOrd for DLList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for DLList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for DLList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for DLList<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for DLList<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for DLList<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<DLList<T>> for Vec<T> {
+    fn from(xs: DLList<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for DLList<T> {
+    fn size(&self) -> usize {
+        DLList::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        DLList::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        DLList::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        DLList::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        DLList::remove(self, i)
+    }
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        DLList::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        DLList::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        DLList::try_remove(self, i)
+    }
+}
+
+impl<T> Queue<T> for DLList<T> {
+    fn push_back(&mut self, x: T) {
+        DLList::add(self, DLList::size(self), x)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        DLList::remove(self, 0)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        DLList::get(self, 0)
+    }
+}
+
+impl<T> Deque<T> for DLList<T> {
+    fn push_front(&mut self, x: T) {
+        DLList::add(self, 0, x)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        DLList::remove(self, DLList::size(self).checked_sub(1)?)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        DLList::get(self, DLList::size(self).checked_sub(1)?)
+    }
+}
+
+impl<T> MemoryUsage for DLList<T> {
+    fn heap_bytes(&self) -> usize {
+        (self.n + 1) * std::mem::size_of::<Node<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        // `dummy` has a slot too.
+        self.n + 1
+    }
+
+    fn wasted_slots(&self) -> usize {
+        1
+    }
+}
+
+pub struct Cursor<'a, T> {
+    index: usize,
+    current: Link<T>,
+    list: &'a DLList<T>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        if self.current == self.list.dummy {
+            return None;
+        }
+        Some(self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        // SAFETY: `current` is always a live node of `list`.
+        self.current = unsafe { (*self.current.as_ptr()).next };
+        self.index = (self.index + 1) % (self.list.n + 1);
+    }
+
+    pub fn move_prev(&mut self) {
+        // SAFETY: `current` is always a live node of `list`.
+        self.current = unsafe { (*self.current.as_ptr()).prev };
+        self.index = (self.index + self.list.n) % (self.list.n + 1);
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        self.list.value(self.current)
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        // SAFETY: `current` is always a live node of `list`.
+        self.list.value(unsafe { (*self.current.as_ptr()).next })
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        // SAFETY: `current` is always a live node of `list`.
+        self.list.value(unsafe { (*self.current.as_ptr()).prev })
+    }
+}
+
+pub struct CursorMut<'a, T> {
+    index: usize,
+    current: Link<T>,
+    list: &'a mut DLList<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        if self.current == self.list.dummy {
+            return None;
+        }
+        Some(self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        // SAFETY: `current` is always a live node of `list`.
+        self.current = unsafe { (*self.current.as_ptr()).next };
+        self.index = (self.index + 1) % (self.list.n + 1);
+    }
+
+    pub fn move_prev(&mut self) {
+        // SAFETY: `current` is always a live node of `list`.
+        self.current = unsafe { (*self.current.as_ptr()).prev };
+        self.index = (self.index + self.list.n) % (self.list.n + 1);
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.list.value_mut(self.current)
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: `current` is always a live node of `list`.
+        self.list
+            .value_mut(unsafe { (*self.current.as_ptr()).next })
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: `current` is always a live node of `list`.
+        self.list
+            .value_mut(unsafe { (*self.current.as_ptr()).prev })
+    }
+
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            index: self.index,
+            current: self.current,
+            list: self.list,
+        }
+    }
+
+    pub fn insert_before(&mut self, x: T) {
+        self.list.add_before(self.current, x);
+        self.index += 1;
+    }
+
+    pub fn insert_after(&mut self, x: T) {
+        // SAFETY: `current` is always a live node of `list`.
+        let next = unsafe { (*self.current.as_ptr()).next };
+        self.list.add_before(next, x);
+        if self.current == self.list.dummy {
+            self.index += 1;
+        }
+    }
+
+    pub fn remove_current(&mut self) -> Option<T> {
+        if self.current == self.list.dummy {
+            return None;
+        }
+        let w = self.current;
+        // SAFETY: `current` is always a live node of `list`.
+        self.current = unsafe { (*w.as_ptr()).next };
+        Some(self.list.remove_node(w))
+    }
+
+    pub fn split_after(&mut self) -> DLList<T> {
+        // SAFETY: `current` and `dummy` are always live nodes of `list`.
+        let (first, last) = unsafe {
+            (
+                (*self.current.as_ptr()).next,
+                (*self.list.dummy.as_ptr()).prev,
+            )
+        };
+        let len = match self.index() {
+            Some(i) => self.list.n - i - 1,
+            None => self.list.n,
+        };
+        let other = self.list.split_nodes(first, last, len);
+        if self.current == self.list.dummy {
+            self.index = 0;
+        }
+        other
+    }
+
+    pub fn split_before(&mut self) -> DLList<T> {
+        // SAFETY: `current` and `dummy` are always live nodes of `list`.
+        let (first, last) = unsafe {
+            (
+                (*self.list.dummy.as_ptr()).next,
+                (*self.current.as_ptr()).prev,
+            )
+        };
+        let other = self.list.split_nodes(first, last, self.index);
+        self.index = 0;
+        other
+    }
+
+    pub fn splice_after(&mut self, other: DLList<T>) {
+        if self.current == self.list.dummy {
+            self.index += other.n;
+        }
+        self.list.splice_nodes(self.current, other);
+    }
+
+    pub fn splice_before(&mut self, other: DLList<T>) {
+        self.index += other.n;
+        // SAFETY: `current` is always a live node of `list`.
+        let prev = unsafe { (*self.current.as_ptr()).prev };
+        self.list.splice_nodes(prev, other);
+    }
+}
+
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `head` is one of the `len` nodes not yet yielded from either end.
+        unsafe {
+            let u = &*self.head.as_ptr();
+            self.head = u.next;
+            Some(u.x.assume_init_ref())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `tail` is one of the `len` nodes not yet yielded from either end.
+        unsafe {
+            let u = &*self.tail.as_ptr();
+            self.tail = u.prev;
+            Some(u.x.assume_init_ref())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `head` is one of the `len` nodes not yet yielded from either end.
+        unsafe {
+            let u = &mut *self.head.as_ptr();
+            self.head = u.next;
+            Some(u.x.assume_init_mut())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `tail` is one of the `len` nodes not yet yielded from either end.
+        unsafe {
+            let u = &mut *self.tail.as_ptr();
+            self.tail = u.prev;
+            Some(u.x.assume_init_mut())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    list: DLList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.remove(0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.n, Some(self.list.n))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.remove(self.list.n.checked_sub(1)?)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DLList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DLList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for DLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add_before(self.dummy, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for DLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut dllist = Self::new();
+        dllist.extend(iter);
+        dllist
     }
 }
 #[cfg(test)]
@@ -129,30 +877,199 @@ mod test {
         dllist.add(0, 5);
         dllist.add(1, 7);
         // dlist: [5, 7, 3, 2]
-        assert_eq!(dllist.get(0), Some(5));
-        assert_eq!(dllist.get(1), Some(7));
-        assert_eq!(dllist.get(2), Some(3));
-        assert_eq!(dllist.get(3), Some(2));
+        assert_eq!(dllist.get(0), Some(&5));
+        assert_eq!(dllist.get(1), Some(&7));
+        assert_eq!(dllist.get(2), Some(&3));
+        assert_eq!(dllist.get(3), Some(&2));
 
         dllist.set(1, 4);
         dllist.set(3, 10);
         // dlist: [5, 4, 3, 10]
-        assert_eq!(dllist.get(1), Some(4));
-        assert_eq!(dllist.get(3), Some(10));
+        assert_eq!(dllist.get(1), Some(&4));
+        assert_eq!(dllist.get(3), Some(&10));
 
-        dllist.remove(2);
+        assert_eq!(dllist.remove(2), Some(3));
         // dlist: [5, 4, 10]
-        assert_eq!(dllist.get(0), Some(5));
-        assert_eq!(dllist.get(1), Some(4));
-        assert_eq!(dllist.get(2), Some(10));
+        assert_eq!(dllist.get(0), Some(&5));
+        assert_eq!(dllist.get(1), Some(&4));
+        assert_eq!(dllist.get(2), Some(&10));
 
-        dllist.remove(0);
+        assert_eq!(dllist.remove(0), Some(5));
         // dlist: [4, 10]
-        assert_eq!(dllist.get(0), Some(4));
-        assert_eq!(dllist.get(1), Some(10));
+        assert_eq!(dllist.get(0), Some(&4));
+        assert_eq!(dllist.get(1), Some(&10));
 
-        dllist.remove(1);
-        dllist.remove(0);
+        assert_eq!(dllist.remove(1), Some(10));
+        assert_eq!(dllist.remove(0), Some(4));
+        assert_eq!(dllist.remove(0), None);
         assert_eq!(dllist.get(0), None);
     }
+
+    #[test]
+    fn dllist_iterators_work() {
+        let mut dllist: DLList<_> = (0..5).collect();
+        assert_eq!(dllist.iter().len(), 5);
+        assert_eq!(
+            format!("{:#?}", dllist)
+                .split_whitespace()
+                .collect::<String>(),
+            "DLList{next:[0,1,2,3,4,],prev:[4,3,2,1,0,],n:5,}"
+        );
+        assert_eq!(dllist.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert_eq!(
+            dllist.iter().rev().copied().collect::<Vec<_>>(),
+            [4, 3, 2, 1, 0]
+        );
+        let mut iter = dllist.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.collect::<Vec<_>>(), [&1, &2, &3]);
+        for x in &mut dllist {
+            *x *= 2;
+        }
+        dllist.extend(vec![10, 12]);
+        let mut into_iter = dllist.into_iter();
+        assert_eq!(into_iter.next_back(), Some(12));
+        assert_eq!(into_iter.collect::<Vec<_>>(), [0, 2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn dllist_owns_its_values() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<DLList<String>>();
+
+        let mut dllist: DLList<String> = DLList::new();
+        dllist.add(0, String::from("b"));
+        dllist.add(0, String::from("a"));
+        dllist.add(2, String::from("c"));
+        dllist.get_mut(1).unwrap().push('!');
+        assert_eq!(dllist.get(1).map(String::as_str), Some("b!"));
+        assert_eq!(dllist.get_mut(3), None);
+
+        let counter = std::rc::Rc::new(());
+        let mut dllist = DLList::new();
+        for i in 0..10 {
+            dllist.add(i, std::rc::Rc::clone(&counter));
+        }
+        dllist.remove(4);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 10);
+        drop(dllist);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn dllist_cursors_work() {
+        let mut dllist: DLList<_> = (0..5).collect();
+        let mut cursor = dllist.cursor(2);
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.peek_prev(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&3));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&0));
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(4));
+        assert_eq!(cursor.current(), Some(&4));
+
+        let mut cursor = dllist.cursor_front_mut();
+        cursor.move_next();
+        *cursor.current().unwrap() = 10;
+        cursor.insert_before(-1);
+        cursor.insert_after(11);
+        // dllist: [0, -1, 10, 11, 2, 3, 4], cursor at 10
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.remove_current(), Some(10));
+        // dllist: [0, -1, 11, 2, 3, 4], cursor at 11
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 11));
+        let tail = cursor.split_after();
+        // dllist: [0, -1, 11], tail: [2, 3, 4]
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+        let head = cursor.split_before();
+        // dllist: [11], head: [0, -1]
+        assert_eq!(cursor.index(), Some(0));
+        cursor.splice_after(tail);
+        cursor.splice_before(head);
+        // dllist: [0, -1, 11, 2, 3, 4], cursor at 11
+        assert_eq!(cursor.index(), Some(2));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        // cursor at the ghost element
+        cursor.insert_before(5);
+        cursor.insert_after(-2);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(dllist.size(), 8);
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [-2, 0, -1, 11, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            dllist.iter().rev().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 11, -1, 0, -2]
+        );
+    }
+
+    #[test]
+    fn dllist_relinking_operations_work() {
+        let mut dllist: DLList<_> = (0..5).collect();
+        let mut other: DLList<_> = (5..8).collect();
+        dllist.append(&mut other);
+        assert_eq!(other.size(), 0);
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 6, 7]
+        );
+
+        let tail = dllist.split_off(6);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), [6, 7]);
+        dllist.splice(2, tail);
+        // dllist: [0, 1, 6, 7, 2, 3, 4, 5]
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 6, 7, 2, 3, 4, 5]
+        );
+        assert_eq!(dllist.size(), 8);
+
+        dllist.reverse();
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 7, 6, 1, 0]
+        );
+        assert_eq!(
+            dllist.iter().rev().copied().collect::<Vec<_>>(),
+            [0, 1, 6, 7, 2, 3, 4, 5]
+        );
+
+        dllist.rotate(3);
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [6, 1, 0, 5, 4, 3, 2, 7]
+        );
+        dllist.rotate(13);
+        assert_eq!(
+            dllist.iter().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 7, 6, 1, 0]
+        );
+        assert_eq!(dllist.get(7), Some(&0));
+
+        let mut rest = dllist.truncate(5);
+        assert_eq!(dllist.iter().copied().collect::<Vec<_>>(), [5, 4, 3, 2, 7]);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), [6, 1, 0]);
+        dllist.absorb(&mut rest);
+        assert_eq!(rest.size(), 0);
+        assert_eq!(dllist.size(), 8);
+
+        let odd = dllist.deal();
+        assert_eq!(dllist.iter().copied().collect::<Vec<_>>(), [5, 3, 7, 1]);
+        assert_eq!(odd.iter().rev().copied().collect::<Vec<_>>(), [0, 6, 2, 4]);
+        assert_eq!((dllist.size(), odd.size()), (4, 4));
+    }
 }
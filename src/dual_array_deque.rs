@@ -1,11 +1,25 @@
-use crate::array_stack::ArrayStack;
+use crate::{
+    array_stack::{self, ArrayStack},
+    error::Error,
+    format,
+    interfaces::{Deque, List, Queue, Stack},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::{self, Ordering},
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Rev,
+    ops::{Index, IndexMut, Range, RangeBounds},
+};
 
+#[derive(Clone)]
 pub struct DualArrayDeque<T> {
     front: ArrayStack<T>,
     back: ArrayStack<T>,
 }
 
-impl<T: Default + Clone> DualArrayDeque<T> {
+impl<T> DualArrayDeque<T> {
     pub fn new() -> Self {
         Self {
             front: ArrayStack::new(),
@@ -17,6 +31,21 @@ impl<T: Default + Clone> DualArrayDeque<T> {
         self.front.size() + self.back.size()
     }
 
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front.iter().rev(),
+            back: self.back.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front.iter_mut().rev(),
+            back: self.back.iter_mut(),
+        }
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
     pub fn get(&self, i: usize) -> Option<&T> {
         if i < self.front.size() {
             self.front.get(self.front.size() - i - 1)
@@ -25,48 +54,461 @@ impl<T: Default + Clone> DualArrayDeque<T> {
         }
     }
 
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i < self.front.size() {
+            self.front.get_mut(self.front.size() - i - 1)
+        } else {
+            self.back.get_mut(i - self.front.size())
+        }
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
     pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.size())?;
         if i < self.front.size() {
-            self.front.set(self.front.size() - i - 1, x)
+            self.front.try_set(self.front.size() - i - 1, x)
         } else {
-            self.back.set(i - self.front.size(), x)
+            self.back.try_set(i - self.front.size(), x)
         }
     }
 
+    /// Inserts `x` at index `i`, then rebalances the two stacks if needed.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
     pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.size())?;
         if i < self.front.size() {
-            self.front.add(self.front.size() - i, x);
+            self.front.try_add(self.front.size() - i, x)?;
         } else {
-            self.back.add(i - self.front.size(), x);
+            self.back.try_add(i - self.front.size(), x)?;
         }
         self.balance();
+        Ok(())
     }
 
-    pub fn balance(&mut self) {
-        let n = self.size();
-        let mid = n / 2;
-        if 3 * self.front.size() < self.back.size() || 3 * self.back.size() < self.front.size() {
-            let mut f = ArrayStack::new();
-            for i in 0..mid {
-                f.add(i, self.get(mid - i - 1).unwrap().clone());
-            }
-            let mut b = ArrayStack::new();
-            for i in 0..n - mid {
-                b.add(i, self.get(mid + i).unwrap().clone());
-            }
-            self.front = f;
-            self.back = b;
+    /// Inserts the elements of `iter` at index `i` with a single bulk insertion
+    /// into one of the two stacks.
+    ///
+    /// Panics if `i > size()`.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, i: usize, iter: I) {
+        if let Err(e) = Error::check_insert(i, self.size()) {
+            panic!("{}", e);
         }
+        let xs = iter.into_iter().collect::<Vec<_>>();
+        if i < self.front.size() {
+            self.front
+                .insert_many(self.front.size() - i, xs.into_iter().rev());
+        } else {
+            self.back.insert_many(i - self.front.size(), xs);
+        }
+        self.balance();
     }
 
+    /// Removes the elements in `range`.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range);
+    }
+
+    /// Removes the elements in `range` and returns them in order.
+    ///
+    /// The elements are removed immediately, even if the iterator is not consumed.
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let Range { start, end } =
+            Error::check_range(range, self.size()).unwrap_or_else(|e| panic!("{}", e));
+        let f = self.front.size();
+        let mut xs = self
+            .front
+            .drain(f - cmp::min(end, f)..f - cmp::min(start, f))
+            .rev()
+            .collect::<Vec<_>>();
+        xs.extend(
+            self.back
+                .drain(cmp::max(start, f) - f..cmp::max(end, f) - f),
+        );
+        self.balance();
+        Drain::new(xs)
+    }
+
+    pub fn push_front(&mut self, x: T) {
+        self.front.push(x);
+        self.balance();
+    }
+
+    pub fn push_back(&mut self, x: T) {
+        self.back.push(x);
+        self.balance();
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // Once balanced, `front` is only empty if `back` holds at most one element.
+        let x = self.front.pop().or_else(|| self.back.pop());
+        self.balance();
+        x
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let x = self.back.pop().or_else(|| self.front.pop());
+        self.balance();
+        x
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.size().checked_sub(1)?)
+    }
+
+    /// Restores `3 * front.size() >= back.size()` and vice versa by moving the
+    /// elements between the middle and the smaller stack's end across in bulk.
+    fn balance(&mut self) {
+        let f = self.front.size();
+        let b = self.back.size();
+        let mid = (f + b) / 2;
+        if 3 * f < b {
+            // front[0] is the element just left of the middle.
+            let xs = self.back.drain(..mid - f).rev().collect::<Vec<_>>();
+            self.front.insert_many(0, xs);
+        } else if 3 * b < f {
+            let xs = self.front.drain(..f - mid).rev().collect::<Vec<_>>();
+            self.back.insert_many(0, xs);
+        }
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
     pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.size())?;
         let x = if i < self.front.size() {
-            self.front.remove(self.front.size() - i - 1)
+            self.front.try_remove(self.front.size() - i - 1)?
         } else {
-            self.back.remove(i - self.front.size())
+            self.back.try_remove(i - self.front.size())?
         };
         self.balance();
-        x
+        Ok(x)
+    }
+}
+
+impl<T> Default for DualArrayDeque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for DualArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("DualArrayDeque")
+                .field("front", &self.front)
+                .field("back", &self.back)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for DualArrayDeque<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size() == other.size() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DualArrayDeque<T> {}
+
+impl<T: PartialOrd> PartialOrd for DualArrayDeque<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for DualArrayDeque<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for DualArrayDeque<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.size());
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for DualArrayDeque<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.size();
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for DualArrayDeque<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.size();
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DualArrayDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for DualArrayDeque<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<DualArrayDeque<T>> for Vec<T> {
+    fn from(xs: DualArrayDeque<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for DualArrayDeque<T> {
+    fn size(&self) -> usize {
+        DualArrayDeque::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        DualArrayDeque::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        DualArrayDeque::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        DualArrayDeque::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        DualArrayDeque::remove(self, i)
+    }
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        DualArrayDeque::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        DualArrayDeque::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        DualArrayDeque::try_remove(self, i)
+    }
+}
+
+impl<T> Queue<T> for DualArrayDeque<T> {
+    fn push_back(&mut self, x: T) {
+        DualArrayDeque::push_back(self, x)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        DualArrayDeque::pop_front(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        DualArrayDeque::front(self)
+    }
+}
+
+impl<T> Deque<T> for DualArrayDeque<T> {
+    fn push_front(&mut self, x: T) {
+        DualArrayDeque::push_front(self, x)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        DualArrayDeque::pop_back(self)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        DualArrayDeque::back(self)
+    }
+}
+
+impl<T> MemoryUsage for DualArrayDeque<T> {
+    fn heap_bytes(&self) -> usize {
+        self.front.heap_bytes() + self.back.heap_bytes()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.front.allocated_slots() + self.back.allocated_slots()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.front.wasted_slots() + self.back.wasted_slots()
+    }
+}
+
+pub struct Iter<'a, T> {
+    front: Rev<array_stack::Iter<'a, T>>,
+    back: array_stack::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(x) => Some(x),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(x) => Some(x),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    front: Rev<array_stack::IterMut<'a, T>>,
+    back: array_stack::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(x) => Some(x),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(x) => Some(x),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub use crate::array_stack::Drain;
+
+pub struct IntoIter<T> {
+    front: Rev<array_stack::IntoIter<T>>,
+    back: array_stack::IntoIter<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.next() {
+            Some(x) => Some(x),
+            None => self.back.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.next_back() {
+            Some(x) => Some(x),
+            None => self.front.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for DualArrayDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            front: self.front.into_iter().rev(),
+            back: self.back.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DualArrayDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DualArrayDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for DualArrayDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_many(self.size(), iter);
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for DualArrayDeque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut dual_array_deque = Self::new();
+        dual_array_deque.extend(iter);
+        dual_array_deque
     }
 }
 
@@ -116,4 +558,100 @@ mod test {
         dual_array_deque.add(0, 10);
         assert_eq!(dual_array_deque.get(0), Some(&10));
     }
+
+    #[test]
+    fn dual_array_deque_holds_move_only_values() {
+        let mut dual_array_deque: DualArrayDeque<Box<dyn Fn() -> usize>> = DualArrayDeque::new();
+        for i in 0..10 {
+            dual_array_deque.add(0, Box::new(move || i));
+        }
+        // deque: [9, 8, ..., 0]
+        for i in 0..10 {
+            assert_eq!(dual_array_deque.get(i).unwrap()(), 9 - i);
+        }
+        assert_eq!(dual_array_deque.remove(9).unwrap()(), 0);
+        assert_eq!(dual_array_deque.remove(0).unwrap()(), 9);
+    }
+
+    #[test]
+    fn dual_array_deque_iterators_work() {
+        let mut dual_array_deque: DualArrayDeque<_> = (3..6).collect();
+        dual_array_deque.add(0, 2);
+        dual_array_deque.add(0, 1);
+        dual_array_deque.extend(6..8);
+        // deque: [1, 2, 3, 4, 5, 6, 7]
+        assert!(dual_array_deque.front.size() > 0);
+        assert_eq!(dual_array_deque.iter().len(), 7);
+        assert_eq!(
+            dual_array_deque.iter().copied().collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(
+            dual_array_deque.iter().rev().copied().collect::<Vec<_>>(),
+            [7, 6, 5, 4, 3, 2, 1]
+        );
+        for x in &mut dual_array_deque {
+            *x *= 2;
+        }
+        let mut into_iter = dual_array_deque.into_iter();
+        assert_eq!(into_iter.next_back(), Some(14));
+        assert_eq!(into_iter.collect::<Vec<_>>(), [2, 4, 6, 8, 10, 12]);
+    }
+
+    #[test]
+    fn dual_array_deque_bulk_operations_work() {
+        let mut dual_array_deque = (0..8).collect::<DualArrayDeque<_>>();
+        dual_array_deque.insert_many(1, 10..13);
+        dual_array_deque.insert_many(9, 20..22);
+        let expected = [0, 10, 11, 12, 1, 2, 3, 4, 5, 20, 21, 6, 7];
+        assert_eq!(
+            dual_array_deque.iter().copied().collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            dual_array_deque.drain(2..10).collect::<Vec<_>>(),
+            expected[2..10]
+        );
+        dual_array_deque.remove_range(..1);
+        assert_eq!(
+            dual_array_deque.iter().copied().collect::<Vec<_>>(),
+            [10, 21, 6, 7]
+        );
+        assert_eq!(dual_array_deque.pop_front(), Some(10));
+        assert_eq!(dual_array_deque.pop_back(), Some(7));
+    }
+
+    #[test]
+    fn dual_array_deque_debug_shows_both_stacks() {
+        let dual_array_deque = DualArrayDeque::from(vec![1, 2, 3]);
+        assert_eq!(format!("{:?}", dual_array_deque), "[1, 2, 3]");
+        let raw = format!("{:#?}", dual_array_deque);
+        assert!(raw.starts_with("DualArrayDeque {"));
+        assert!(raw.contains("front: ArrayStack {"));
+        assert!(raw.contains("back: ArrayStack {"));
+    }
+
+    #[test]
+    fn dual_array_deque_stays_balanced() {
+        let mut dual_array_deque = DualArrayDeque::new();
+        let mut expected = std::collections::VecDeque::new();
+        for i in 0..50 {
+            dual_array_deque.push_back(i);
+            expected.push_back(i);
+        }
+        for i in 0..30 {
+            assert_eq!(dual_array_deque.pop_front(), expected.pop_front());
+            dual_array_deque.push_front(-i);
+            expected.push_front(-i);
+            assert_eq!(dual_array_deque.pop_front(), expected.pop_front());
+            assert!(3 * dual_array_deque.front.size() >= dual_array_deque.back.size());
+            assert!(3 * dual_array_deque.back.size() >= dual_array_deque.front.size());
+        }
+        assert!(dual_array_deque.iter().eq(expected.iter()));
+        while let Some(x) = dual_array_deque.pop_back() {
+            assert_eq!(Some(x), expected.pop_back());
+        }
+        assert_eq!(dual_array_deque.front(), None);
+        assert_eq!(expected.len(), 0);
+    }
 }
@@ -0,0 +1,120 @@
+use std::{
+    fmt,
+    ops::{Bound, Range, RangeBounds},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IndexOutOfBounds { index: usize, len: usize },
+    Empty,
+    CapacityExceeded,
+}
+
+impl Error {
+    pub(crate) fn check_index(index: usize, len: usize) -> Result<(), Error> {
+        if index < len {
+            Ok(())
+        } else {
+            Err(Error::IndexOutOfBounds { index, len })
+        }
+    }
+
+    pub(crate) fn check_insert(index: usize, len: usize) -> Result<(), Error> {
+        if index <= len {
+            Ok(())
+        } else {
+            Err(Error::IndexOutOfBounds { index, len })
+        }
+    }
+
+    pub(crate) fn check_remove(index: usize, len: usize) -> Result<(), Error> {
+        if len == 0 {
+            return Err(Error::Empty);
+        }
+        Error::check_index(index, len)
+    }
+
+    /// Resolves `range` against a sequence of length `len`.
+    pub(crate) fn check_range<R: RangeBounds<usize>>(
+        range: R,
+        len: usize,
+    ) -> Result<Range<usize>, Error> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        if end > len {
+            Err(Error::IndexOutOfBounds { index: end, len })
+        } else if start > end {
+            Err(Error::IndexOutOfBounds {
+                index: start,
+                len: end,
+            })
+        } else {
+            Ok(start..end)
+        }
+    }
+
+    /// Checks that a backing array of `len` slots of `S` can be allocated.
+    pub(crate) fn check_capacity<S>(len: usize) -> Result<(), Error> {
+        match len.checked_mul(std::mem::size_of::<S>()) {
+            Some(bytes) if bytes <= isize::MAX as usize => Ok(()),
+            _ => Err(Error::CapacityExceeded),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::IndexOutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "index out of bounds: the len is {} but the index is {}",
+                    len, index
+                )
+            }
+            Error::Empty => write!(f, "the container is empty"),
+            Error::CapacityExceeded => write!(f, "capacity exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn error_works() {
+        assert_eq!(Error::check_index(2, 3), Ok(()));
+        assert_eq!(
+            Error::check_index(3, 3),
+            Err(Error::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(Error::check_insert(3, 3), Ok(()));
+        assert_eq!(Error::check_remove(0, 0), Err(Error::Empty));
+        assert_eq!(Error::check_range(1..=2, 3), Ok(1..3));
+        assert_eq!(Error::check_range(.., 3), Ok(0..3));
+        assert_eq!(
+            Error::check_range(2..4, 3),
+            Err(Error::IndexOutOfBounds { index: 4, len: 3 })
+        );
+        assert_eq!(
+            Error::check_capacity::<u64>(usize::MAX / 4),
+            Err(Error::CapacityExceeded)
+        );
+        assert_eq!(Error::check_capacity::<u64>(1024), Ok(()));
+        assert_eq!(Error::check_capacity::<()>(usize::MAX), Ok(()));
+        assert_eq!(
+            Error::IndexOutOfBounds { index: 5, len: 2 }.to_string(),
+            "index out of bounds: the len is 2 but the index is 5"
+        );
+    }
+}
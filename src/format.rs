@@ -0,0 +1,39 @@
+use std::fmt;
+
+/// Writes `xs` as `[x0, x1, ...]` using each element's `Display`.
+pub(crate) fn display_list<'a, T, I>(f: &mut fmt::Formatter<'_>, xs: I) -> fmt::Result
+where
+    T: fmt::Display + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    display_delimited(f, "[", xs, "]")
+}
+
+/// Writes `xs` as `{x0, x1, ...}` using each element's `Display`.
+pub(crate) fn display_set<'a, T, I>(f: &mut fmt::Formatter<'_>, xs: I) -> fmt::Result
+where
+    T: fmt::Display + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    display_delimited(f, "{", xs, "}")
+}
+
+fn display_delimited<'a, T, I>(
+    f: &mut fmt::Formatter<'_>,
+    open: &str,
+    xs: I,
+    close: &str,
+) -> fmt::Result
+where
+    T: fmt::Display + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    write!(f, "{}", open)?;
+    for (i, x) in xs.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", x)?;
+    }
+    write!(f, "{}", close)
+}
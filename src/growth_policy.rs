@@ -0,0 +1,121 @@
+use crate::error::Error;
+use std::cmp;
+
+/// How an array-backed structure resizes its backing array.
+///
+/// When full, the array grows to `growth_factor` times its length. After a
+/// removal, if the array is at least `shrink_threshold` times longer than the
+/// number of elements, it shrinks to `growth_factor` times that number. The
+/// default is ODS's policy: double when full, shrink when `a.len() >= 3n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthPolicy {
+    growth_factor: usize,
+    shrink_threshold: Option<usize>,
+}
+
+impl GrowthPolicy {
+    /// Panics unless `growth_factor >= 2` and `shrink_threshold > growth_factor`,
+    /// which keeps a resize from immediately triggering the opposite one.
+    pub fn new(growth_factor: usize, shrink_threshold: usize) -> Self {
+        assert!(
+            growth_factor >= 2,
+            "growth factor (is {}) should be >= 2",
+            growth_factor
+        );
+        assert!(
+            shrink_threshold > growth_factor,
+            "shrink threshold (is {}) should be > growth factor (is {})",
+            shrink_threshold,
+            growth_factor
+        );
+        Self {
+            growth_factor,
+            shrink_threshold: Some(shrink_threshold),
+        }
+    }
+
+    /// A policy that grows by `growth_factor` but only shrinks on `shrink_to_fit`.
+    pub fn never_shrink(growth_factor: usize) -> Self {
+        assert!(
+            growth_factor >= 2,
+            "growth factor (is {}) should be >= 2",
+            growth_factor
+        );
+        Self {
+            growth_factor,
+            shrink_threshold: None,
+        }
+    }
+
+    pub fn growth_factor(&self) -> usize {
+        self.growth_factor
+    }
+
+    pub fn shrink_threshold(&self) -> Option<usize> {
+        self.shrink_threshold
+    }
+
+    /// The length to grow a backing array of `S` from `capacity` to so that it
+    /// holds at least `required` slots.
+    pub(crate) fn grow<S>(&self, capacity: usize, required: usize) -> Result<usize, Error> {
+        Error::check_capacity::<S>(required)?;
+        let grown = capacity.saturating_mul(self.growth_factor);
+        if Error::check_capacity::<S>(grown).is_ok() {
+            Ok(cmp::max(1, cmp::max(grown, required)))
+        } else {
+            Ok(cmp::max(1, required))
+        }
+    }
+
+    /// The length to shrink a backing array of length `capacity` holding `n`
+    /// elements to, if it should shrink at all.
+    pub(crate) fn shrink(&self, capacity: usize, n: usize) -> Option<usize> {
+        let threshold = self.shrink_threshold?;
+        let target = cmp::max(1, n.saturating_mul(self.growth_factor));
+        if capacity >= n.saturating_mul(threshold) && target < capacity {
+            Some(target)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for GrowthPolicy {
+    fn default() -> Self {
+        Self::new(2, 3)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn growth_policy_works() {
+        let policy = GrowthPolicy::default();
+        assert_eq!(policy.grow::<u64>(0, 1), Ok(1));
+        assert_eq!(policy.grow::<u64>(4, 5), Ok(8));
+        assert_eq!(policy.grow::<u64>(4, 20), Ok(20));
+        assert_eq!(
+            policy.grow::<u64>(4, usize::MAX),
+            Err(Error::CapacityExceeded)
+        );
+        assert_eq!(policy.shrink(8, 3), None);
+        assert_eq!(policy.shrink(9, 3), Some(6));
+        assert_eq!(policy.shrink(1, 0), None);
+
+        let policy = GrowthPolicy::new(4, 8);
+        assert_eq!(policy.grow::<u64>(4, 5), Ok(16));
+        assert_eq!(policy.shrink(16, 3), None);
+        assert_eq!(policy.shrink(16, 2), Some(8));
+
+        let policy = GrowthPolicy::never_shrink(2);
+        assert_eq!(policy.shrink_threshold(), None);
+        assert_eq!(policy.shrink(1024, 0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn growth_policy_rejects_thrashing() {
+        GrowthPolicy::new(3, 3);
+    }
+}
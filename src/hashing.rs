@@ -0,0 +1,359 @@
+use crate::rng::Rng;
+use std::{
+    fmt,
+    hash::{BuildHasher, Hasher},
+    sync::Arc,
+};
+
+/// The input bytes as little-endian `u64` words, the last one zero-padded.
+fn words(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    bytes.chunks(8).map(|chunk| {
+        let mut word = [0; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u64::from_le_bytes(word)
+    })
+}
+
+/// Multiplicative hashing: `z * x mod 2^64` for a random odd `z`, whose high
+/// bits are the well-mixed ones; a table with 2^d slots takes
+/// `hash >> (64 - d)`. `finish` also xors the high half into the low half,
+/// so tables that take the low bits, like `std`'s, spread keys too. Longer
+/// inputs are folded in one word at a time.
+#[derive(Debug, Clone)]
+pub struct Multiplicative {
+    z: u64,
+}
+
+impl Multiplicative {
+    /// Draws `z` from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_rng(Rng::from_entropy())
+    }
+
+    /// Draws `z` from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(Rng::new(seed))
+    }
+
+    fn with_rng(mut rng: Rng) -> Self {
+        Self {
+            z: rng.next_u64() | 1,
+        }
+    }
+}
+
+impl Default for Multiplicative {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for Multiplicative {
+    type Hasher = MultiplicativeHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        MultiplicativeHasher { z: self.z, h: 0 }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MultiplicativeHasher {
+    z: u64,
+    h: u64,
+}
+
+impl Hasher for MultiplicativeHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for word in words(bytes) {
+            self.h = (self.h ^ word).wrapping_mul(self.z);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.h ^ (self.h >> 32)
+    }
+}
+
+/// Simple tabulation hashing (ODS 5.2.3): each byte of a 32-bit key word
+/// indexes its own table of random 64-bit entries and the four entries are
+/// xored, so the hash codes of distinct words are 3-wise independent and
+/// linear probing on them takes expected O(1) probes.
+///
+/// Inputs are first reduced to that one word outside the tables, by the fixed
+/// fold `31 * h + w` over their 4-byte words, as a Java `hashCode` would; a
+/// `u32` or `i32` key is its own word. The guarantees are about distinct
+/// words: inputs that fold to the same word always collide.
+#[derive(Clone)]
+pub struct Tabulation {
+    tab: Arc<[[u64; 256]; 4]>,
+}
+
+impl Tabulation {
+    /// Fills the tables from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_rng(Rng::from_entropy())
+    }
+
+    /// Fills the tables from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(Rng::new(seed))
+    }
+
+    fn with_rng(mut rng: Rng) -> Self {
+        let mut tab = [[0; 256]; 4];
+        for row in tab.iter_mut() {
+            for entry in row.iter_mut() {
+                *entry = rng.next_u64();
+            }
+        }
+        Self { tab: Arc::new(tab) }
+    }
+}
+
+impl Default for Tabulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Tabulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tabulation").finish_non_exhaustive()
+    }
+}
+
+impl BuildHasher for Tabulation {
+    type Hasher = TabulationHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        TabulationHasher {
+            tab: Arc::clone(&self.tab),
+            word: 0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TabulationHasher {
+    tab: Arc<[[u64; 256]; 4]>,
+    word: u32,
+}
+
+impl fmt::Debug for TabulationHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TabulationHasher")
+            .field("word", &self.word)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Hasher for TabulationHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(4) {
+            let mut w = [0; 4];
+            w[..chunk.len()].copy_from_slice(chunk);
+            self.word = self
+                .word
+                .wrapping_mul(31)
+                .wrapping_add(u32::from_le_bytes(w));
+        }
+    }
+
+    /// Looks up each byte of the key word once, in its own table.
+    fn finish(&self) -> u64 {
+        self.word
+            .to_le_bytes()
+            .iter()
+            .zip(self.tab.iter())
+            .fold(0, |h, (&b, row)| h ^ row[b as usize])
+    }
+}
+
+/// The Mersenne prime 2^61 - 1 that polynomial hashing works modulo.
+const P: u64 = (1 << 61) - 1;
+
+fn mul_mod_p(a: u64, b: u64) -> u64 {
+    let x = a as u128 * b as u128;
+    let x = (x & P as u128) + (x >> 61);
+    let x = x as u64;
+    if x >= P {
+        x - P
+    } else {
+        x
+    }
+}
+
+/// Polynomial hashing of sequences: the input is read as 32-bit words
+/// `x0, ..., x(k-1)`, followed by the byte count, and hashed to
+/// `x0 z^k + ... + x(k-1) z + len mod p` for p = 2^61 - 1 and a random `z`
+/// in `1..p`. Two different inputs of at most `r` words collide with
+/// probability at most `(r + 1) / p`. `finish` passes that residue through a
+/// fixed bijection of `u64`, which keeps the bound but spreads the codes over
+/// all 64 bits instead of `0..p`.
+#[derive(Debug, Clone)]
+pub struct Polynomial {
+    z: u64,
+}
+
+impl Polynomial {
+    /// Draws `z` from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_rng(Rng::from_entropy())
+    }
+
+    /// Draws `z` from a generator seeded with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(Rng::new(seed))
+    }
+
+    fn with_rng(mut rng: Rng) -> Self {
+        Self {
+            z: 1 + rng.next_u64() % (P - 1),
+        }
+    }
+}
+
+impl Default for Polynomial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for Polynomial {
+    type Hasher = PolynomialHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        PolynomialHasher {
+            z: self.z,
+            h: 0,
+            len: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PolynomialHasher {
+    z: u64,
+    h: u64,
+    len: u64,
+}
+
+impl PolynomialHasher {
+    /// The polynomial's value mod p, before `finish` scrambles it.
+    fn residue(&self) -> u64 {
+        (mul_mod_p(self.h, self.z) + self.len % P) % P
+    }
+}
+
+impl Hasher for PolynomialHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(4) {
+            let mut word = [0; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.h = (mul_mod_p(self.h, self.z) + u32::from_le_bytes(word) as u64) % P;
+        }
+        self.len += bytes.len() as u64;
+    }
+
+    fn finish(&self) -> u64 {
+        let h = self.residue().wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        h ^ (h >> 32)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{collections::HashSet, hash::Hash};
+
+    fn hash<S: BuildHasher, T: Hash + ?Sized>(s: &S, x: &T) -> u64 {
+        s.hash_one(x)
+    }
+
+    #[test]
+    fn hashing_is_reproducible() {
+        let xs = ["", "a", "ab", "open data structures"];
+        for &x in xs.iter() {
+            assert_eq!(
+                hash(&Multiplicative::with_seed(3), x),
+                hash(&Multiplicative::with_seed(3), x)
+            );
+            assert_eq!(
+                hash(&Tabulation::with_seed(3), x),
+                hash(&Tabulation::with_seed(3), x)
+            );
+            assert_eq!(
+                hash(&Polynomial::with_seed(3), x),
+                hash(&Polynomial::with_seed(3), x)
+            );
+        }
+        assert_ne!(
+            hash(&Tabulation::with_seed(3), "a"),
+            hash(&Tabulation::with_seed(4), "a")
+        );
+    }
+
+    #[test]
+    fn multiplicative_hashing_works() {
+        let s = Multiplicative::with_seed(9);
+        let mut h = s.build_hasher();
+        h.write_u64(12345);
+        let product = 12345u64.wrapping_mul(s.z);
+        assert_eq!(h.finish(), product ^ (product >> 32));
+        let slots = (0..64u64)
+            .map(|x| hash(&s, &x) >> (64 - 8))
+            .collect::<HashSet<_>>();
+        assert!(slots.len() > 48);
+    }
+
+    #[test]
+    fn multiplicative_hashing_spreads_low_bits() {
+        let s = Multiplicative::with_seed(1);
+        let keys = (0..1000u64).map(|k| k << 32).collect::<Vec<_>>();
+        let low = keys
+            .iter()
+            .map(|k| hash(&s, k) & 0xff)
+            .collect::<HashSet<_>>();
+        assert!(low.len() > 200);
+        let mut set = HashSet::with_hasher(s);
+        set.extend(keys.iter().copied());
+        assert_eq!(set.len(), 1000);
+        assert!(keys.iter().all(|k| set.contains(k)));
+        assert!(!set.contains(&1));
+    }
+
+    #[test]
+    fn tabulation_hashing_works() {
+        let s = Tabulation::with_seed(9);
+        assert_ne!(hash(&s, &[0u8][..]), hash(&s, &[0u8, 0][..]));
+        let x = 0x1234_5678u32;
+        assert_eq!(
+            hash(&s, &x),
+            s.tab[0][0x78] ^ s.tab[1][0x56] ^ s.tab[2][0x34] ^ s.tab[3][0x12]
+        );
+        assert_eq!(hash(&s, &-1i32), hash(&s, &u32::MAX));
+        let codes = (0..1000u32).map(|x| hash(&s, &x)).collect::<HashSet<_>>();
+        assert_eq!(codes.len(), 1000);
+        let mut set = HashSet::with_hasher(s);
+        set.extend(0..100);
+        assert!((0..100).all(|x| set.contains(&x)));
+        assert!(!set.contains(&100));
+    }
+
+    #[test]
+    fn polynomial_hashing_works() {
+        let s = Polynomial::with_seed(9);
+        let mut h = s.build_hasher();
+        h.write(&[1, 0, 0, 0, 2, 0, 0, 0]);
+        let z = s.z as u128;
+        let p = P as u128;
+        assert_eq!(h.residue() as u128, (z * z + 2 * z + 8) % p);
+        assert_ne!(hash(&s, "ab"), hash(&s, "ba"));
+        assert_ne!(hash(&s, &[0u8][..]), hash(&s, &[0u8, 0][..]));
+        let top = (0..1000u32)
+            .map(|x| hash(&s, &x) >> 56)
+            .collect::<HashSet<_>>();
+        assert!(top.len() > 200);
+        assert_eq!(mul_mod_p(P - 1, P - 1), 1);
+    }
+}
@@ -0,0 +1,342 @@
+use crate::error::Error;
+
+/// The List interface: a sequence indexed by `0..size()`.
+///
+/// `get`, `set` and `remove` return `None` for an index outside `0..size()`,
+/// and `add` panics for an index greater than `size()`; the `try_` variants
+/// report these cases as an [`Error`] instead.
+pub trait List<T> {
+    fn size(&self) -> usize;
+    fn get(&self, i: usize) -> Option<&T>;
+    fn set(&mut self, i: usize, x: T) -> Option<T>;
+    fn add(&mut self, i: usize, x: T);
+    fn remove(&mut self, i: usize) -> Option<T>;
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error>;
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error>;
+    fn try_remove(&mut self, i: usize) -> Result<T, Error>;
+}
+
+/// The Queue interface: elements are added at the back and removed from the front.
+pub trait Queue<T> {
+    fn push_back(&mut self, x: T);
+    fn pop_front(&mut self) -> Option<T>;
+    fn peek_front(&self) -> Option<&T>;
+}
+
+/// The Deque interface: a Queue that can also be added to and removed from at the other end.
+pub trait Deque<T>: Queue<T> {
+    fn push_front(&mut self, x: T);
+    fn pop_back(&mut self) -> Option<T>;
+    fn peek_back(&self) -> Option<&T>;
+}
+
+/// The Stack interface: elements are added and removed at the same end.
+pub trait Stack<T> {
+    fn push(&mut self, x: T);
+    fn pop(&mut self) -> Option<T>;
+    fn peek(&self) -> Option<&T>;
+}
+
+/// The USet interface: an unordered set of distinct elements.
+pub trait USet<T: Eq> {
+    fn size(&self) -> usize;
+    /// Adds `x` unless an equal element is present; returns whether it was added.
+    fn add(&mut self, x: T) -> bool;
+    /// Removes and returns the element equal to `x`, if any.
+    fn remove(&mut self, x: &T) -> Option<T>;
+    /// Returns the element equal to `x`, if any.
+    fn find(&self, x: &T) -> Option<&T>;
+}
+
+/// The SSet interface: a sorted set. `find(x)` returns the smallest element
+/// that is at least `x`, so a search for a missing element still succeeds.
+pub trait SSet<T: Ord> {
+    fn size(&self) -> usize;
+    /// Adds `x` unless an equal element is present; returns whether it was added.
+    fn add(&mut self, x: T) -> bool;
+    /// Removes and returns the element equal to `x`, if any.
+    fn remove(&mut self, x: &T) -> Option<T>;
+    fn find(&self, x: &T) -> Option<&T>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array_deque::ArrayDeque, array_queue::ArrayQueue, array_stack::ArrayStack,
+        chained_hash_table::ChainedHashTable, deamortized_array_stack::DeamortizedArrayStack,
+        dllist::DLList, dual_array_deque::DualArrayDeque, linear_hash_table::LinearHashTable,
+        optimal_array_stack::OptimalArrayStack, rootish_array_stack::RootishArrayStack,
+        selist::SEList, skiplist_list::SkiplistList, skiplist_sset::SkiplistSSet, sllist::SLList,
+        tiered_vector::TieredVector,
+    };
+    use std::{
+        collections::hash_map::DefaultHasher,
+        fmt,
+        hash::{Hash, Hasher},
+        ops::IndexMut,
+    };
+
+    fn exercise<L: List<i32>>(mut list: L) {
+        assert_eq!(list.size(), 0);
+        assert_eq!(list.remove(0), None);
+        for i in 0..10 {
+            list.add(i as usize, i);
+        }
+        list.add(0, -1);
+        list.add(5, 100);
+        // list: [-1, 0, 1, 2, 3, 100, 4, 5, 6, 7, 8, 9]
+        assert_eq!(list.size(), 12);
+        assert_eq!(list.get(0), Some(&-1));
+        assert_eq!(list.get(5), Some(&100));
+        assert_eq!(list.get(11), Some(&9));
+        assert_eq!(list.get(12), None);
+        assert_eq!(list.set(5, 50), Some(100));
+        assert_eq!(list.get(5), Some(&50));
+        assert_eq!(list.remove(5), Some(50));
+        assert_eq!(list.remove(0), Some(-1));
+        // list: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+        for i in 0..10 {
+            assert_eq!(list.get(i), Some(&(i as i32)));
+        }
+        for i in (0..10).rev() {
+            assert_eq!(list.remove(i), Some(i as i32));
+        }
+        assert_eq!(list.size(), 0);
+    }
+
+    fn exercise_checked<L: List<i32>>(mut list: L) {
+        assert_eq!(list.try_remove(0), Err(Error::Empty));
+        assert_eq!(
+            list.try_add(1, 0),
+            Err(Error::IndexOutOfBounds { index: 1, len: 0 })
+        );
+        assert_eq!(list.try_add(0, 1), Ok(()));
+        assert_eq!(list.try_add(1, 3), Ok(()));
+        assert_eq!(list.try_add(1, 2), Ok(()));
+        // list: [1, 2, 3]
+        assert_eq!(
+            list.try_set(3, 4),
+            Err(Error::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(list.try_set(2, 4), Ok(3));
+        assert_eq!(
+            list.try_remove(3),
+            Err(Error::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(list.remove(5), None);
+        assert_eq!(list.set(5, 0), None);
+        assert_eq!(list.size(), 3);
+        assert_eq!(list.try_remove(1), Ok(2));
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(1), Some(&4));
+    }
+
+    #[test]
+    fn list_implementations_check_indices() {
+        exercise_checked(ArrayStack::new());
+        exercise_checked(DeamortizedArrayStack::new());
+        exercise_checked(OptimalArrayStack::new());
+        exercise_checked(ArrayDeque::new());
+        exercise_checked(DualArrayDeque::new());
+        exercise_checked(RootishArrayStack::new());
+        exercise_checked(TieredVector::new());
+        exercise_checked(DLList::new());
+        exercise_checked(SEList::new());
+        exercise_checked(SkiplistList::new());
+        exercise_checked(SLList::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 0 but the index is 1")]
+    fn list_add_panics_past_the_end() {
+        List::add(&mut ArrayStack::new(), 1, 0);
+    }
+
+    #[test]
+    fn list_implementations_work() {
+        exercise(ArrayStack::new());
+        exercise(DeamortizedArrayStack::new());
+        exercise(OptimalArrayStack::new());
+        exercise(ArrayDeque::new());
+        exercise(DualArrayDeque::new());
+        exercise(RootishArrayStack::new());
+        exercise(TieredVector::new());
+        exercise(DLList::new());
+        exercise(SEList::new());
+        exercise(SkiplistList::new());
+        exercise(SLList::new());
+    }
+
+    fn exercise_traits<L>()
+    where
+        L: List<i32>
+            + Clone
+            + Ord
+            + Hash
+            + IndexMut<usize, Output = i32>
+            + fmt::Display
+            + fmt::Debug
+            + From<Vec<i32>>,
+        Vec<i32>: From<L>,
+    {
+        let hash = |list: &L| {
+            let mut state = DefaultHasher::new();
+            list.hash(&mut state);
+            state.finish()
+        };
+        let mut list = L::from(vec![1, 2, 3]);
+        let mut other = list.clone();
+        assert_eq!(list, other);
+        assert_eq!(hash(&list), hash(&other));
+        other[2] = 4;
+        assert_eq!(other[2], 4);
+        assert!(list < other);
+        list.add(3, 0);
+        assert!(list < other);
+        assert_ne!(list, other);
+        assert_eq!(list.to_string(), "[1, 2, 3, 0]");
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 0]");
+        assert_eq!(Vec::from(list), [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn list_implementations_have_standard_traits() {
+        exercise_traits::<ArrayStack<i32>>();
+        exercise_traits::<DeamortizedArrayStack<i32>>();
+        exercise_traits::<OptimalArrayStack<i32>>();
+        exercise_traits::<ArrayDeque<i32>>();
+        exercise_traits::<DualArrayDeque<i32>>();
+        exercise_traits::<RootishArrayStack<i32>>();
+        exercise_traits::<TieredVector<i32>>();
+        exercise_traits::<DLList<i32>>();
+        exercise_traits::<SEList<i32>>();
+        exercise_traits::<SkiplistList<i32>>();
+        exercise_traits::<SLList<i32>>();
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn list_index_panics_out_of_bounds() {
+        let _ = DLList::from(vec![1, 2, 3])[3];
+    }
+
+    fn exercise_queue<Q: Queue<i32>>(mut queue: Q) {
+        assert_eq!(queue.pop_front(), None);
+        assert_eq!(queue.peek_front(), None);
+        for i in 0..10 {
+            queue.push_back(i);
+        }
+        assert_eq!(queue.peek_front(), Some(&0));
+        for i in 0..5 {
+            assert_eq!(queue.pop_front(), Some(i));
+        }
+        queue.push_back(10);
+        for i in 5..11 {
+            assert_eq!(queue.peek_front(), Some(&i));
+            assert_eq!(queue.pop_front(), Some(i));
+        }
+        assert_eq!(queue.pop_front(), None);
+    }
+
+    fn exercise_deque<D: Deque<i32>>(mut deque: D) {
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.peek_back(), None);
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        deque.push_front(0);
+        // deque: [0, 1, 2, 3]
+        assert_eq!(deque.peek_front(), Some(&0));
+        assert_eq!(deque.peek_back(), Some(&3));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_back(), Some(1));
+        assert_eq!(deque.pop_front(), None);
+        exercise_queue(deque);
+    }
+
+    fn exercise_stack<S: Stack<i32>>(mut stack: S) {
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+        for i in 0..10 {
+            stack.push(i);
+            assert_eq!(stack.peek(), Some(&i));
+        }
+        for i in (0..10).rev() {
+            assert_eq!(stack.pop(), Some(i));
+        }
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn queue_implementations_work() {
+        exercise_queue(ArrayQueue::new());
+        exercise_queue(SLList::new());
+    }
+
+    #[test]
+    fn deque_implementations_work() {
+        exercise_deque(ArrayDeque::new());
+        exercise_deque(DualArrayDeque::new());
+        exercise_deque(DLList::new());
+        exercise_deque(SEList::new());
+    }
+
+    #[test]
+    fn stack_implementations_work() {
+        exercise_stack(ArrayStack::new());
+        exercise_stack(DeamortizedArrayStack::new());
+        exercise_stack(OptimalArrayStack::new());
+        exercise_stack(SLList::new());
+    }
+
+    fn exercise_sset<S: SSet<i32>>(mut sset: S) {
+        assert_eq!(sset.find(&0), None);
+        assert_eq!(sset.remove(&0), None);
+        for x in [5, 1, 9, 3, 7].iter() {
+            assert!(sset.add(*x));
+        }
+        assert!(!sset.add(3));
+        // sset: {1, 3, 5, 7, 9}
+        assert_eq!(sset.size(), 5);
+        assert_eq!(sset.find(&3), Some(&3));
+        assert_eq!(sset.find(&4), Some(&5));
+        assert_eq!(sset.find(&0), Some(&1));
+        assert_eq!(sset.find(&10), None);
+        assert_eq!(sset.remove(&4), None);
+        assert_eq!(sset.remove(&5), Some(5));
+        assert_eq!(sset.find(&4), Some(&7));
+        assert_eq!(sset.size(), 4);
+    }
+
+    #[test]
+    fn sset_implementations_work() {
+        exercise_sset(SkiplistSSet::new());
+    }
+
+    fn exercise_uset<U: USet<i32>>(mut uset: U) {
+        assert_eq!(uset.find(&0), None);
+        assert_eq!(uset.remove(&0), None);
+        for x in 0..100 {
+            assert!(uset.add(x * 3));
+        }
+        assert!(!uset.add(6));
+        assert_eq!(uset.size(), 100);
+        assert_eq!(uset.find(&6), Some(&6));
+        assert_eq!(uset.find(&7), None);
+        for x in 0..50 {
+            assert_eq!(uset.remove(&(x * 6)), Some(x * 6));
+        }
+        assert_eq!(uset.remove(&6), None);
+        assert_eq!(uset.find(&3), Some(&3));
+        assert_eq!(uset.size(), 50);
+    }
+
+    #[test]
+    fn uset_implementations_work() {
+        exercise_uset(ChainedHashTable::new());
+        exercise_uset(LinearHashTable::new());
+    }
+}
@@ -1,7 +1,28 @@
 pub mod array_deque;
 pub mod array_queue;
 pub mod array_stack;
+mod bounded_deque;
+pub mod chained_hash_table;
+pub mod deamortized_array_stack;
 pub mod dllist;
 pub mod dual_array_deque;
+pub mod error;
+mod format;
+pub mod growth_policy;
+pub mod hashing;
+pub mod interfaces;
+pub mod linear_hash_table;
+pub mod memory;
+pub mod optimal_array_stack;
+mod rng;
 pub mod rootish_array_stack;
+pub mod selist;
+pub mod skiplist_list;
+pub mod skiplist_sset;
 pub mod sllist;
+pub mod tiered_vector;
+
+pub use error::Error;
+pub use growth_policy::GrowthPolicy;
+pub use interfaces::{Deque, List, Queue, SSet, Stack, USet};
+pub use memory::MemoryUsage;
@@ -0,0 +1,478 @@
+use crate::{format, hashing::Tabulation, interfaces::USet, memory::MemoryUsage};
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+#[derive(Debug, Clone)]
+enum Slot<T> {
+    Empty,
+    Deleted,
+    Full(T),
+}
+
+/// An open-addressing hash table with linear probing. Removed elements
+/// leave a `Deleted` tombstone so later probes still pass over them; `q`
+/// counts live elements plus tombstones and the table is rebuilt before
+/// `2q` exceeds its length, so at least half of the slots are always empty.
+/// Slots come from the hash codes of `S`. The default is the simple 4×8-bit
+/// tabulation hashing of ODS 5.2.3 on each key's 32-bit word, under which
+/// the expected probe length is O(1).
+#[derive(Clone)]
+pub struct LinearHashTable<T, S = Tabulation> {
+    t: Box<[Slot<T>]>,
+    d: u32,
+    n: usize,
+    q: usize,
+    hasher: S,
+}
+
+/// Probe lengths of the elements in a `LinearHashTable`: the number of
+/// slots a successful `find` inspects, counting the one it stops at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProbeStats {
+    pub average: f64,
+    pub max: usize,
+}
+
+impl<T: Hash + Eq> LinearHashTable<T> {
+    /// Creates an empty table whose tabulation entries are drawn from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_hasher(Tabulation::new())
+    }
+
+    /// Creates an empty table whose tabulation entries are drawn from a
+    /// generator seeded with `seed`, so the same operations fill the same slots.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_hasher(Tabulation::with_seed(seed))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> LinearHashTable<T, S> {
+    /// Creates an empty table whose slots come from the top bits of the
+    /// hash codes that `hasher` builds, so those bits should be well mixed.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            t: allocate(2),
+            d: 1,
+            n: 0,
+            q: 0,
+            hasher,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: self.t.iter(),
+            len: self.n,
+        }
+    }
+
+    /// The slot for `x`: the top `d` bits of its hash code.
+    fn hash(&self, x: &T) -> usize {
+        (self.hasher.hash_one(x) >> (64 - self.d)) as usize
+    }
+
+    /// The slot holding `x`, if any.
+    fn position(&self, x: &T) -> Option<usize> {
+        let mut i = self.hash(x);
+        loop {
+            match &self.t[i] {
+                Slot::Empty => return None,
+                Slot::Full(y) if y == x => return Some(i),
+                _ => i = (i + 1) & (self.t.len() - 1),
+            }
+        }
+    }
+
+    /// Returns the element equal to `x`, or `None` if there is none.
+    pub fn find(&self, x: &T) -> Option<&T> {
+        match &self.t[self.position(x)?] {
+            Slot::Full(y) => Some(y),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.position(x).is_some()
+    }
+
+    /// Adds `x` unless an equal element is present; returns whether it was added.
+    /// The new element reuses the first tombstone on its probe path.
+    pub fn add(&mut self, x: T) -> bool {
+        if self.contains(&x) {
+            return false;
+        }
+        if 2 * (self.q + 1) > self.t.len() {
+            self.resize();
+        }
+        let i = self.vacant_slot(&x);
+        if let Slot::Empty = self.t[i] {
+            self.q += 1;
+        }
+        self.t[i] = Slot::Full(x);
+        self.n += 1;
+        true
+    }
+
+    /// Removes and returns the element equal to `x`, or `None` if there is none.
+    pub fn remove(&mut self, x: &T) -> Option<T> {
+        let i = self.position(x)?;
+        let y = match std::mem::replace(&mut self.t[i], Slot::Deleted) {
+            Slot::Full(y) => y,
+            _ => unreachable!(),
+        };
+        self.n -= 1;
+        if 8 * self.n < self.t.len() && self.d > 1 {
+            self.resize();
+        }
+        Some(y)
+    }
+
+    pub fn clear(&mut self) {
+        self.t = allocate(2);
+        self.d = 1;
+        self.n = 0;
+        self.q = 0;
+    }
+
+    /// Reports the average and maximum probe length over the live elements,
+    /// or zeros when the table is empty.
+    pub fn probe_stats(&self) -> ProbeStats {
+        let mask = self.t.len() - 1;
+        let mut total = 0;
+        let mut max = 0;
+        for (i, slot) in self.t.iter().enumerate() {
+            if let Slot::Full(x) = slot {
+                let probes = (i.wrapping_sub(self.hash(x)) & mask) + 1;
+                total += probes;
+                max = std::cmp::max(max, probes);
+            }
+        }
+        ProbeStats {
+            average: if self.n == 0 {
+                0.0
+            } else {
+                total as f64 / self.n as f64
+            },
+            max,
+        }
+    }
+
+    /// The first empty or deleted slot on the probe path of `x`.
+    fn vacant_slot(&self, x: &T) -> usize {
+        let mut i = self.hash(x);
+        while let Slot::Full(_) = self.t[i] {
+            i = (i + 1) & (self.t.len() - 1);
+        }
+        i
+    }
+
+    /// Rebuilds the table with the smallest 2^d >= 3n slots (at least 2),
+    /// dropping every tombstone.
+    fn resize(&mut self) {
+        self.d = 1;
+        while 1 << self.d < 3 * self.n {
+            self.d += 1;
+        }
+        let old = std::mem::replace(&mut self.t, allocate(1 << self.d));
+        for slot in old.into_vec() {
+            if let Slot::Full(x) = slot {
+                let i = self.vacant_slot(&x);
+                self.t[i] = Slot::Full(x);
+            }
+        }
+        self.q = self.n;
+    }
+}
+
+fn allocate<T>(len: usize) -> Box<[Slot<T>]> {
+    std::iter::repeat_with(|| Slot::Empty).take(len).collect()
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> Default for LinearHashTable<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+/// Formats as the set of elements; `{:#?}` shows the slots instead.
+impl<T: fmt::Debug, S> fmt::Debug for LinearHashTable<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("LinearHashTable")
+                .field("t", &self.t)
+                .field("n", &self.n)
+                .field("q", &self.q)
+                .finish()
+        } else {
+            f.debug_set()
+                .entries(self.t.iter().filter_map(|slot| match slot {
+                    Slot::Full(x) => Some(x),
+                    _ => None,
+                }))
+                .finish()
+        }
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> PartialEq for LinearHashTable<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().all(|x| other.contains(x))
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> Eq for LinearHashTable<T, S> {}
+
+impl<T: Hash + Eq + fmt::Display, S: BuildHasher> fmt::Display for LinearHashTable<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_set(f, self.iter())
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> From<Vec<T>> for LinearHashTable<T, S> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T, S> From<LinearHashTable<T, S>> for Vec<T> {
+    fn from(xs: LinearHashTable<T, S>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> USet<T> for LinearHashTable<T, S> {
+    fn size(&self) -> usize {
+        LinearHashTable::size(self)
+    }
+
+    fn add(&mut self, x: T) -> bool {
+        LinearHashTable::add(self, x)
+    }
+
+    fn remove(&mut self, x: &T) -> Option<T> {
+        LinearHashTable::remove(self, x)
+    }
+
+    fn find(&self, x: &T) -> Option<&T> {
+        LinearHashTable::find(self, x)
+    }
+}
+
+impl<T, S> MemoryUsage for LinearHashTable<T, S> {
+    fn heap_bytes(&self) -> usize {
+        self.t.len() * std::mem::size_of::<Slot<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.t.len()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.t.len() - self.n
+    }
+}
+
+/// Yields the elements in slot order, which is no particular order.
+pub struct Iter<'a, T> {
+    inner: std::slice::Iter<'a, Slot<T>>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in &mut self.inner {
+            if let Slot::Full(x) = slot {
+                self.len -= 1;
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IntoIter<T> {
+    inner: std::vec::IntoIter<Slot<T>>,
+    len: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for slot in &mut self.inner {
+            if let Slot::Full(x) = slot {
+                self.len -= 1;
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T, S> IntoIterator for LinearHashTable<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.t.into_vec().into_iter(),
+            len: self.n,
+        }
+    }
+}
+
+impl<'a, T: Hash + Eq, S: BuildHasher> IntoIterator for &'a LinearHashTable<T, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> Extend<T> for LinearHashTable<T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher + Default> std::iter::FromIterator<T> for LinearHashTable<T, S> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut linear_hash_table = Self::with_hasher(S::default());
+        linear_hash_table.extend(iter);
+        linear_hash_table
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn linear_hash_table_works() {
+        let mut linear_hash_table = LinearHashTable::with_seed(5);
+        let mut expected = HashSet::new();
+        for i in 0..1000 {
+            let x = (i * 37) % 601;
+            assert_eq!(linear_hash_table.add(x), expected.insert(x));
+            assert!(2 * linear_hash_table.q <= linear_hash_table.t.len());
+        }
+        assert_eq!(linear_hash_table.size(), expected.len());
+        assert_eq!(
+            linear_hash_table.iter().collect::<HashSet<_>>(),
+            expected.iter().collect()
+        );
+        for i in 0..700 {
+            let x = (i * 13) % 650;
+            assert_eq!(linear_hash_table.remove(&x), expected.take(&x));
+            let y = (i * 7) % 700;
+            assert_eq!(linear_hash_table.find(&y), expected.get(&y));
+            assert!(linear_hash_table.size() <= linear_hash_table.q);
+            if i % 3 == 0 {
+                assert_eq!(linear_hash_table.add(x), expected.insert(x));
+            }
+        }
+        assert_eq!(linear_hash_table.size(), expected.len());
+        let mut xs = Vec::from(linear_hash_table);
+        xs.sort();
+        let mut ys = expected.into_iter().collect::<Vec<_>>();
+        ys.sort();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn linear_hash_table_uses_simple_tabulation() {
+        let mut linear_hash_table = LinearHashTable::<u32>::with_seed(8);
+        linear_hash_table.extend(0..1000);
+        // Each byte is looked up in its own table and the results xored, so
+        // keys that pair two values in each of two bytes cancel out.
+        let h = |x: u32| linear_hash_table.hash(&x);
+        for &(a, b) in [(0x12, 0x34), (0xff, 0x00), (0x07, 0x70)].iter() {
+            let (x0, x1) = (a, b);
+            let (y0, y1) = (a << 16, b << 16);
+            assert_eq!(h(x0 | y0) ^ h(x1 | y0) ^ h(x0 | y1) ^ h(x1 | y1), 0);
+        }
+        assert!(linear_hash_table.probe_stats().average < 3.0);
+    }
+
+    #[test]
+    fn linear_hash_table_reports_probe_stats() {
+        let mut linear_hash_table = LinearHashTable::with_seed(11);
+        assert_eq!(
+            linear_hash_table.probe_stats(),
+            ProbeStats {
+                average: 0.0,
+                max: 0
+            }
+        );
+        linear_hash_table.add(42);
+        assert_eq!(
+            linear_hash_table.probe_stats(),
+            ProbeStats {
+                average: 1.0,
+                max: 1
+            }
+        );
+        linear_hash_table.extend(0..5000);
+        let stats = linear_hash_table.probe_stats();
+        assert!(stats.average >= 1.0 && stats.average < 4.0);
+        assert!(stats.max >= 1 && stats.max < 100);
+    }
+
+    #[test]
+    fn linear_hash_table_compares_as_a_set() {
+        let a = (0..20).collect::<LinearHashTable<_>>();
+        let mut b = (0..20).rev().collect::<LinearHashTable<_>>();
+        assert_eq!(a, b);
+        b.remove(&3);
+        b.add(20);
+        assert_ne!(a, b);
+        let mut c = LinearHashTable::new();
+        c.add("x");
+        assert_eq!(c.to_string(), "{x}");
+        c.clear();
+        assert_eq!(c.size(), 0);
+        assert_eq!(format!("{:?}", c), "{}");
+    }
+
+    #[test]
+    fn linear_hash_table_takes_any_hasher() {
+        use crate::hashing::Polynomial;
+        use std::collections::hash_map::RandomState;
+        let mut a = LinearHashTable::with_hasher(Polynomial::with_seed(2));
+        let mut b = LinearHashTable::with_hasher(RandomState::new());
+        for x in 0..500 {
+            assert!(a.add(x * 7));
+            assert!(b.add(x * 7));
+        }
+        assert!(!a.add(14));
+        assert_eq!(a.find(&21), Some(&21));
+        assert_eq!(b.remove(&21), Some(21));
+        assert_eq!(b.find(&21), None);
+        assert_eq!(a.size(), 500);
+        assert_eq!(b.size(), 499);
+    }
+}
@@ -0,0 +1,57 @@
+/// Reports how much memory a container holds on the heap. A slot is a place
+/// for one element; the bytes that elements themselves own are not counted.
+pub trait MemoryUsage {
+    /// The bytes allocated by the container: its slots plus any node links
+    /// and block indexes.
+    fn heap_bytes(&self) -> usize;
+    /// The number of element slots allocated.
+    fn allocated_slots(&self) -> usize;
+    /// The number of allocated slots that hold no element.
+    fn wasted_slots(&self) -> usize;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array_stack::ArrayStack, dllist::DLList, dual_array_deque::DualArrayDeque,
+        rootish_array_stack::RootishArrayStack, selist::SEList, sllist::SLList,
+    };
+    use std::mem::size_of;
+
+    #[test]
+    fn memory_usage_works() {
+        let array_stack = (0..100u64).collect::<ArrayStack<_>>();
+        assert_eq!(array_stack.allocated_slots(), array_stack.capacity());
+        assert_eq!(array_stack.wasted_slots(), array_stack.capacity() - 100);
+        assert_eq!(
+            array_stack.heap_bytes(),
+            array_stack.capacity() * size_of::<Option<u64>>()
+        );
+
+        let dual_array_deque = (0..100u64).collect::<DualArrayDeque<_>>();
+        assert_eq!(
+            dual_array_deque.allocated_slots() - dual_array_deque.wasted_slots(),
+            100
+        );
+
+        // blocks of 1, 2, ..., 14 hold 105 slots
+        let rootish_array_stack = (0..100u64).collect::<RootishArrayStack<_>>();
+        assert_eq!(rootish_array_stack.allocated_slots(), 105);
+        assert_eq!(rootish_array_stack.wasted_slots(), 5);
+        assert!(rootish_array_stack.heap_bytes() > 105 * size_of::<u64>());
+
+        let sllist = (0..100u64).collect::<SLList<_>>();
+        assert_eq!(sllist.wasted_slots(), 0);
+        let dllist = (0..100u64).collect::<DLList<_>>();
+        assert_eq!(dllist.wasted_slots(), 1);
+        // each node carries its own links
+        assert!(dllist.heap_bytes() > sllist.heap_bytes());
+
+        // 100 elements fill blocks of b + 1 = 9 with one to spare
+        let selist = (0..100u64).collect::<SEList<_>>();
+        assert_eq!(selist.allocated_slots(), 108);
+        assert_eq!(selist.wasted_slots(), 8);
+        assert!(selist.heap_bytes() < dllist.heap_bytes());
+    }
+}
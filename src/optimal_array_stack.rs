@@ -0,0 +1,465 @@
+use crate::{
+    array_deque,
+    array_stack::ArrayStack,
+    error::Error,
+    format,
+    interfaces::{List, Stack},
+    memory::MemoryUsage,
+    rootish_array_stack::{layout, Block},
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{self, MaybeUninit},
+    ops::{Index, IndexMut},
+};
+
+/// The resizable array of Brodnik et al.: index `i` lives in superblock
+/// `k = floor(log2(i + 1))`, which holds 2^floor(k/2) blocks of 2^ceil(k/2)
+/// slots each. Blocks are allocated uninitialized, one at a time, and
+/// elements are never copied to grow, and at most one empty block is kept, so
+/// only O(√n) slots are wasted.
+///
+/// The index of blocks is an ArrayStack, which copies its O(√n) entries when
+/// it resizes, so appending is O(1) amortized, not worst-case.
+pub struct OptimalArrayStack<T> {
+    n: usize,
+    capacity: usize,
+    blocks: ArrayStack<Block<T>>,
+}
+
+impl<T> OptimalArrayStack<T> {
+    pub fn new() -> Self {
+        Self {
+            n: 0,
+            capacity: 0,
+            blocks: ArrayStack::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// The number of slots in the allocated blocks that hold no element.
+    pub fn wasted_space(&self) -> usize {
+        self.capacity - self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.blocks.iter(), self.n, self.capacity)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self.blocks.iter_mut(), self.n, self.capacity)
+    }
+
+    /// Returns `(block, offset)` for index `i`, read off the bits of `i + 1`:
+    /// after its leading one come floor(k/2) bits selecting the block within
+    /// superblock `k` and ceil(k/2) bits giving the offset.
+    fn locate(i: usize) -> (usize, usize) {
+        let r = i + 1;
+        let k = (usize::BITS - 1 - r.leading_zeros()) as usize;
+        let (lo, hi) = (k / 2, k - k / 2);
+        // Superblocks before k hold 2 * (2^(k/2) - 1) blocks for even k,
+        // and 3 * 2^((k-1)/2) - 2 for odd k.
+        let before = if k & 1 == 0 {
+            (2 << lo) - 2
+        } else {
+            (3 << lo) - 2
+        };
+        let b = (r >> hi) & ((1 << lo) - 1);
+        (before + b, r & ((1 << hi) - 1))
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        let (b, j) = OptimalArrayStack::<T>::locate(i);
+        // SAFETY: the slots of indices below `n` are initialized.
+        Some(unsafe { self.blocks.get(b)?[j].assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
+        }
+        let (b, j) = OptimalArrayStack::<T>::locate(i);
+        // SAFETY: the slots of indices below `n` are initialized.
+        Some(unsafe { self.blocks.get_mut(b)?[j].assume_init_mut() })
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+
+    /// Inserts `x` at index `i`, shifting later elements up.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        if self.n == self.capacity {
+            self.grow()?;
+        }
+        for m in (i..self.n).rev() {
+            let y = mem::replace(self.slot(m), MaybeUninit::uninit());
+            *self.slot(m + 1) = y;
+        }
+        *self.slot(i) = MaybeUninit::new(x);
+        self.n += 1;
+        Ok(())
+    }
+
+    /// Allocates the block starting at index `capacity`, in O(1) apart from
+    /// the occasional resize of the block index.
+    fn grow(&mut self) -> Result<(), Error> {
+        let k = (usize::BITS - 1 - (self.capacity + 1).leading_zeros()) as usize;
+        let block_size = 1 << (k - k / 2);
+        self.blocks
+            .try_add(self.blocks.size(), array_deque::allocate(block_size))?;
+        self.capacity += block_size;
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        // SAFETY: index `i` is below `n`; the shifts below move the slot it
+        // leaves behind to index `n - 1`.
+        let x = unsafe { self.slot(i).assume_init_read() };
+        for m in i + 1..self.n {
+            let y = mem::replace(self.slot(m), MaybeUninit::uninit());
+            *self.slot(m - 1) = y;
+        }
+        self.n -= 1;
+        self.shrink();
+        Ok(x)
+    }
+
+    /// The slot holding index `i`, which may lie past `n` within the last block.
+    fn slot(&mut self, i: usize) -> &mut MaybeUninit<T> {
+        let (b, j) = OptimalArrayStack::<T>::locate(i);
+        &mut self.blocks.get_mut(b).unwrap()[j]
+    }
+
+    /// Frees the last block while the one before it is also empty.
+    fn shrink(&mut self) {
+        let mut r = self.blocks.size();
+        while r >= 2 {
+            let last = self.blocks.get(r - 1).unwrap().len();
+            let second_last = self.blocks.get(r - 2).unwrap().len();
+            if self.capacity - last - second_last < self.n {
+                break;
+            }
+            self.blocks.remove(r - 1);
+            self.capacity -= last;
+            r -= 1;
+        }
+    }
+}
+
+impl<T> Drop for OptimalArrayStack<T> {
+    fn drop(&mut self) {
+        for x in self.iter_mut() {
+            // SAFETY: each element is dropped once and not used again.
+            unsafe { std::ptr::drop_in_place(x) }
+        }
+    }
+}
+
+impl<T: Clone> Clone for OptimalArrayStack<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for OptimalArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for OptimalArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("OptimalArrayStack")
+                .field("blocks", &layout(&self.blocks, self.n))
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for OptimalArrayStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for OptimalArrayStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for OptimalArrayStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for OptimalArrayStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for OptimalArrayStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for OptimalArrayStack<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for OptimalArrayStack<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for OptimalArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for OptimalArrayStack<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<OptimalArrayStack<T>> for Vec<T> {
+    fn from(xs: OptimalArrayStack<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for OptimalArrayStack<T> {
+    fn size(&self) -> usize {
+        OptimalArrayStack::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        OptimalArrayStack::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        OptimalArrayStack::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        OptimalArrayStack::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        OptimalArrayStack::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        OptimalArrayStack::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        OptimalArrayStack::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        OptimalArrayStack::try_remove(self, i)
+    }
+}
+
+impl<T> Stack<T> for OptimalArrayStack<T> {
+    fn push(&mut self, x: T) {
+        OptimalArrayStack::add(self, self.n, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        OptimalArrayStack::remove(self, self.n.checked_sub(1)?)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        OptimalArrayStack::get(self, self.n.checked_sub(1)?)
+    }
+}
+
+impl<T> MemoryUsage for OptimalArrayStack<T> {
+    fn heap_bytes(&self) -> usize {
+        self.capacity * mem::size_of::<MaybeUninit<T>>() + self.blocks.heap_bytes()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.capacity
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.wasted_space()
+    }
+}
+
+pub use crate::rootish_array_stack::{IntoIter, Iter, IterMut};
+
+impl<T> IntoIterator for OptimalArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let n = mem::take(&mut self.n);
+        IntoIter::new(mem::take(&mut self.blocks).into_iter(), n, self.capacity)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OptimalArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut OptimalArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for OptimalArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for OptimalArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut optimal_array_stack = Self::new();
+        optimal_array_stack.extend(iter);
+        optimal_array_stack
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::array_stack::ArrayStack;
+    #[test]
+    fn optimal_array_stack_works() {
+        let mut optimal_array_stack = OptimalArrayStack::new();
+        let mut expected = Vec::new();
+        for i in 0..100 {
+            let k = (i * 7) % (expected.len() + 1);
+            optimal_array_stack.add(k, i);
+            expected.insert(k, i);
+        }
+        for (i, x) in expected.iter().enumerate() {
+            assert_eq!(optimal_array_stack.get(i), Some(x));
+        }
+        assert_eq!(optimal_array_stack.set(3, 1000), Some(expected[3]));
+        expected[3] = 1000;
+        while !expected.is_empty() {
+            let k = (expected.len() * 5) % (expected.len() + 3) % expected.len();
+            assert_eq!(optimal_array_stack.remove(k), Some(expected.remove(k)));
+        }
+        assert_eq!(optimal_array_stack.remove(0), None);
+        assert_eq!(optimal_array_stack.wasted_space(), 1);
+    }
+
+    #[test]
+    fn optimal_array_stack_locates_by_superblock() {
+        // superblocks: [0] [1 2] [3 4 | 5 6] [7 8 9 10 | 11 12 13 14] ...
+        let locate = OptimalArrayStack::<()>::locate;
+        assert_eq!(locate(0), (0, 0));
+        assert_eq!(locate(2), (1, 1));
+        assert_eq!(locate(5), (3, 0));
+        assert_eq!(locate(10), (4, 3));
+        assert_eq!(locate(11), (5, 0));
+        assert_eq!(locate(15), (6, 0));
+    }
+
+    #[test]
+    fn optimal_array_stack_wastes_sqrt_n_space() {
+        let mut optimal_array_stack = OptimalArrayStack::new();
+        let mut array_stack = ArrayStack::new();
+        for i in 0..10_000 {
+            optimal_array_stack.push(i);
+            array_stack.add(i, i);
+            assert!(optimal_array_stack.wasted_space() <= 4 * (i + 1).isqrt() + 2);
+        }
+        assert!(optimal_array_stack.wasted_space() < array_stack.wasted_space());
+        for _ in 0..9_000 {
+            optimal_array_stack.pop();
+        }
+        assert!(optimal_array_stack.wasted_space() <= 4 * 1_000usize.isqrt() + 2);
+        assert_eq!(optimal_array_stack.iter().next_back(), Some(&999));
+    }
+
+    #[test]
+    fn optimal_array_stack_drops_each_element_once() {
+        use std::rc::Rc;
+        let x = Rc::new(());
+        let mut optimal_array_stack = (0..10)
+            .map(|_| Rc::clone(&x))
+            .collect::<OptimalArrayStack<_>>();
+        optimal_array_stack.add(3, Rc::clone(&x));
+        drop(optimal_array_stack.remove(7));
+        assert_eq!(Rc::strong_count(&x), 11);
+        let copy = optimal_array_stack.clone();
+        let mut iter = optimal_array_stack.into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        drop(copy);
+        assert_eq!(Rc::strong_count(&x), 1);
+    }
+}
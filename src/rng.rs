@@ -0,0 +1,51 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// SplitMix64: a small generator whose whole state is one `u64`, so a seed
+/// fixes the entire stream. Not for cryptographic use.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds from the per-process randomness of `RandomState`.
+    pub(crate) fn from_entropy() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns `k` with probability 2^-(k+1), capped at 63: the number of
+    /// trailing ones in a random word.
+    pub(crate) fn next_geometric(&mut self) -> usize {
+        std::cmp::min(self.next_u64().trailing_ones(), 63) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn rng_is_reproducible() {
+        let mut rng = Rng::new(1234567);
+        let xs = (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>();
+        let mut rng = Rng::new(1234567);
+        assert_eq!(xs, (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>());
+        let mut rng = Rng::new(0);
+        // the first output of SplitMix64 seeded with 0
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+}
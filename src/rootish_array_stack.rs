@@ -1,12 +1,28 @@
-use crate::array_stack::ArrayStack;
+use crate::{
+    array_deque::{self, Slots},
+    array_stack::{self, ArrayStack},
+    error::Error,
+    format,
+    interfaces::List,
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Index, IndexMut, Range, RangeBounds},
+};
+
+/// A block of slots, of which only a prefix holds elements.
+pub(crate) type Block<T> = Box<[MaybeUninit<T>]>;
 
-#[derive(Debug)]
 pub struct RootishArrayStack<T> {
     n: usize,
-    blocks: ArrayStack<Box<[Option<T>]>>,
+    blocks: ArrayStack<Block<T>>,
 }
 
-impl<T: Default + Clone> RootishArrayStack<T> {
+impl<T> RootishArrayStack<T> {
     pub fn new() -> Self {
         Self {
             n: 0,
@@ -14,84 +30,688 @@ impl<T: Default + Clone> RootishArrayStack<T> {
         }
     }
 
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// The number of slots in the allocated blocks that hold no element.
+    pub fn wasted_space(&self) -> usize {
+        let r = self.blocks.size();
+        r * (r + 1) / 2 - self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.blocks.iter(), self.n, self.allocated_slots())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let capacity = self.allocated_slots();
+        IterMut::new(self.blocks.iter_mut(), self.n, capacity)
+    }
+
     fn i2b(i: usize) -> usize {
-        ((-3.0 + (9.0 + 8.0 * (i as f64)).sqrt()) / 2.0).ceil() as usize
+        // The largest b with b(b+1)/2 <= i, i.e. floor((sqrt(8i + 1) - 1) / 2).
+        ((((8 * i as u128) + 1).isqrt() - 1) / 2) as usize
     }
 
-    pub fn get(&self, i: usize) -> Option<&T> {
+    fn locate(i: usize) -> (usize, usize) {
         let b = RootishArrayStack::<T>::i2b(i);
-        let j = i - b * (b + 1) / 2;
-        match self.blocks.get(b) {
-            Some(block) => block[j].as_ref(),
-            None => None,
+        (b, i - b * (b + 1) / 2)
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
         }
+        let (b, j) = RootishArrayStack::<T>::locate(i);
+        // SAFETY: the slots of indices below `n` are initialized.
+        Some(unsafe { self.blocks.get(b)?[j].assume_init_ref() })
     }
 
-    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
-        let b = RootishArrayStack::<T>::i2b(i);
-        let j = i - b * (b + 1) / 2;
-        match self.blocks.get_mut(b) {
-            Some(block) => {
-                let y = block[j].take();
-                block[j] = Some(x);
-                y
-            }
-            None => None,
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
         }
+        let (b, j) = RootishArrayStack::<T>::locate(i);
+        // SAFETY: the slots of indices below `n` are initialized.
+        Some(unsafe { self.blocks.get_mut(b)?[j].assume_init_mut() })
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
     }
 
+    /// Inserts `x` at index `i`, shifting later elements up one block at a time.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
     pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
         let r = self.blocks.size();
         if r * (r + 1) / 2 < self.n + 1 {
-            self.grow();
+            self.grow()?;
         }
-        self.n += 1;
-        for j in (i + 1..self.n).rev() {
-            if let Some(x) = self.get(j - 1) {
-                let x = x.clone();
-                self.set(j, x);
-            }
+        // Shift the tail of each block right by one, carrying the element
+        // that falls off its end into the front of the next block, up to the
+        // block holding the new last index, whose last slot is free.
+        let (mut b, mut j) = RootishArrayStack::<T>::locate(i);
+        let (last, _) = RootishArrayStack::<T>::locate(self.n);
+        let mut carry = MaybeUninit::new(x);
+        while b <= last {
+            let block = self.blocks.get_mut(b).unwrap();
+            block[j..].rotate_right(1);
+            mem::swap(&mut block[j], &mut carry);
+            b += 1;
+            j = 0;
         }
-        self.set(i, x);
+        self.n += 1;
+        Ok(())
     }
 
-    fn grow(&mut self) {
+    fn grow(&mut self) -> Result<(), Error> {
         let block_size = self.blocks.size();
-        let block = std::iter::repeat_with(|| None)
-            .take(block_size + 1)
-            .collect::<Vec<_>>()
-            .into_boxed_slice();
-        self.blocks.add(block_size, block);
+        self.blocks
+            .try_add(block_size, array_deque::allocate(block_size + 1))
     }
 
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
     pub fn remove(&mut self, i: usize) -> Option<T> {
-        if self.n == 0 {
-            return None;
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        // Shift the tail of each block left by one, carrying the front of
+        // the next block into the slot this leaves free at its end.
+        let (mut b, j) = RootishArrayStack::<T>::locate(i);
+        let block = self.blocks.get_mut(b).unwrap();
+        // SAFETY: index `i` is below `n`; the shifts below move the slot it
+        // leaves behind to index `n - 1`.
+        let x = unsafe { block[j].assume_init_read() };
+        block[j..].rotate_left(1);
+        while b * (b + 1) / 2 + b + 1 < self.n {
+            let next = self.blocks.get_mut(b + 1).unwrap();
+            let y = mem::replace(&mut next[0], MaybeUninit::uninit());
+            next.rotate_left(1);
+            *self.blocks.get_mut(b).unwrap().last_mut().unwrap() = y;
+            b += 1;
         }
-        let x = self.get(i).map(|x| x.clone())?;
-        for j in i..self.n - 1 {
-            if let Some(x) = self.get(j + 1) {
-                let x = x.clone();
-                self.set(j, x);
+        self.n -= 1;
+        let r = self.blocks.size();
+        if r.saturating_sub(2) * r.saturating_sub(1) / 2 >= self.n {
+            self.shrink();
+        }
+        Ok(x)
+    }
+
+    /// Inserts the elements of `iter` at index `i`, growing the block list once
+    /// and moving each later element directly to its final slot.
+    ///
+    /// Panics if `i > size()`.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, i: usize, iter: I) {
+        if let Err(e) = Error::check_insert(i, self.n) {
+            panic!("{}", e);
+        }
+        let xs = iter.into_iter().collect::<Vec<_>>();
+        let k = xs.len();
+        let mut r = self.blocks.size();
+        while r * (r + 1) / 2 < self.n + k {
+            if let Err(e) = self.grow() {
+                panic!("{}", e);
             }
+            r += 1;
         }
-        self.n -= 1;
+        for m in (i..self.n).rev() {
+            let x = mem::replace(self.slot(m), MaybeUninit::uninit());
+            *self.slot(m + k) = x;
+        }
+        for (m, x) in xs.into_iter().enumerate() {
+            *self.slot(i + m) = MaybeUninit::new(x);
+        }
+        self.n += k;
+    }
+
+    /// Removes the elements in `range`, moving each later element directly
+    /// to its final slot.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range);
+    }
+
+    /// Removes the elements in `range` and returns them in order.
+    ///
+    /// The elements are removed immediately, even if the iterator is not consumed.
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let Range { start, end } =
+            Error::check_range(range, self.n).unwrap_or_else(|e| panic!("{}", e));
+        // SAFETY: indices `start..end` are below `n`, and the shifts below
+        // move the slots they leave behind past the new `n`.
+        let xs = (start..end)
+            .map(|m| unsafe { self.slot(m).assume_init_read() })
+            .collect::<Vec<_>>();
+        for m in end..self.n {
+            let x = mem::replace(self.slot(m), MaybeUninit::uninit());
+            *self.slot(m - (end - start)) = x;
+        }
+        self.n -= end - start;
         let r = self.blocks.size();
         if r.saturating_sub(2) * r.saturating_sub(1) / 2 >= self.n {
             self.shrink();
         }
-        Some(x)
+        Drain::new(xs)
+    }
+
+    /// The slot holding index `i`, which may lie past `n` within the last blocks.
+    fn slot(&mut self, i: usize) -> &mut MaybeUninit<T> {
+        let (b, j) = RootishArrayStack::<T>::locate(i);
+        &mut self.blocks.get_mut(b).unwrap()[j]
     }
 
     fn shrink(&mut self) {
         let mut r = self.blocks.size();
-        while r > 0 && r.saturating_sub(2) * r.saturating_sub(1) >= self.n {
+        while r > 0 && r.saturating_sub(2) * r.saturating_sub(1) / 2 >= self.n {
             self.blocks.remove(self.blocks.size() - 1);
             r -= 1;
         }
     }
 }
 
+impl<T> Drop for RootishArrayStack<T> {
+    fn drop(&mut self) {
+        for x in self.iter_mut() {
+            // SAFETY: each element is dropped once and not used again.
+            unsafe { std::ptr::drop_in_place(x) }
+        }
+    }
+}
+
+impl<T: Clone> Clone for RootishArrayStack<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for RootishArrayStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for RootishArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("RootishArrayStack")
+                .field("blocks", &layout(&self.blocks, self.n))
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for RootishArrayStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RootishArrayStack<T> {}
+
+impl<T: PartialOrd> PartialOrd for RootishArrayStack<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for RootishArrayStack<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for RootishArrayStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for RootishArrayStack<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RootishArrayStack<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for RootishArrayStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for RootishArrayStack<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<RootishArrayStack<T>> for Vec<T> {
+    fn from(xs: RootishArrayStack<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for RootishArrayStack<T> {
+    fn size(&self) -> usize {
+        RootishArrayStack::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        RootishArrayStack::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        RootishArrayStack::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        RootishArrayStack::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        RootishArrayStack::remove(self, i)
+    }
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        RootishArrayStack::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        RootishArrayStack::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        RootishArrayStack::try_remove(self, i)
+    }
+}
+
+impl<T> MemoryUsage for RootishArrayStack<T> {
+    fn heap_bytes(&self) -> usize {
+        self.allocated_slots() * mem::size_of::<MaybeUninit<T>>() + self.blocks.heap_bytes()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        let r = self.blocks.size();
+        r * (r + 1) / 2
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.wasted_space()
+    }
+}
+
+/// The number of elements in a block whose first slot holds index `start`.
+fn filled(len: usize, start: usize, n: usize) -> usize {
+    n.saturating_sub(start).min(len)
+}
+
+/// The elements of a block whose first slot holds index `start`.
+fn prefix<T>(block: &[MaybeUninit<T>], start: usize, n: usize) -> &[T] {
+    let block = &block[..filled(block.len(), start, n)];
+    // SAFETY: the slots of indices below `n` are initialized.
+    unsafe { &*(block as *const [MaybeUninit<T>] as *const [T]) }
+}
+
+fn prefix_mut<T>(block: &mut [MaybeUninit<T>], start: usize, n: usize) -> &mut [T] {
+    let k = filled(block.len(), start, n);
+    let block = &mut block[..k];
+    // SAFETY: the slots of indices below `n` are initialized.
+    unsafe { &mut *(block as *mut [MaybeUninit<T>] as *mut [T]) }
+}
+
+fn into_prefix<T>(block: Block<T>, start: usize, n: usize) -> Vec<T> {
+    let k = filled(block.len(), start, n);
+    let mut block = ManuallyDrop::new(block.into_vec());
+    // SAFETY: `MaybeUninit<T>` has the layout of `T`, and the first `k`
+    // slots are initialized; the rest are forgotten.
+    unsafe { Vec::from_raw_parts(block.as_mut_ptr() as *mut T, k, block.capacity()) }
+}
+
+/// The slots of `blocks`, for `{:#?}`, with those past index `n` shown as `None`.
+pub(crate) fn layout<T>(blocks: &ArrayStack<Block<T>>, n: usize) -> Vec<Slots<'_, T>> {
+    let mut start = 0;
+    blocks
+        .iter()
+        .map(|block| {
+            let slots = Slots::new(block, 0, filled(block.len(), start, n));
+            start += block.len();
+            slots
+        })
+        .collect()
+}
+
+pub struct Iter<'a, T> {
+    blocks: array_stack::Iter<'a, Block<T>>,
+    front: std::slice::Iter<'a, T>,
+    back: std::slice::Iter<'a, T>,
+    start: usize,
+    end: usize,
+    n: usize,
+    len: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Iterates over the first `len` elements stored in `blocks`, which hold
+    /// `capacity` slots in all.
+    pub(crate) fn new(
+        blocks: array_stack::Iter<'a, Block<T>>,
+        len: usize,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            blocks,
+            front: [].iter(),
+            back: [].iter(),
+            start: 0,
+            end: capacity,
+            n: len,
+            len,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => {
+                    self.front = prefix(block, self.start, self.n).iter();
+                    self.start += block.len();
+                }
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => {
+                    self.end -= block.len();
+                    self.back = prefix(block, self.end, self.n).iter();
+                }
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    blocks: array_stack::IterMut<'a, Block<T>>,
+    front: std::slice::IterMut<'a, T>,
+    back: std::slice::IterMut<'a, T>,
+    start: usize,
+    end: usize,
+    n: usize,
+    len: usize,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    pub(crate) fn new(
+        blocks: array_stack::IterMut<'a, Block<T>>,
+        len: usize,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            blocks,
+            front: [].iter_mut(),
+            back: [].iter_mut(),
+            start: 0,
+            end: capacity,
+            n: len,
+            len,
+        }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => {
+                    let len = block.len();
+                    self.front = prefix_mut(block, self.start, self.n).iter_mut();
+                    self.start += len;
+                }
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => {
+                    self.end -= block.len();
+                    self.back = prefix_mut(block, self.end, self.n).iter_mut();
+                }
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub use crate::array_stack::Drain;
+
+pub struct IntoIter<T> {
+    blocks: array_stack::IntoIter<Block<T>>,
+    front: std::vec::IntoIter<T>,
+    back: std::vec::IntoIter<T>,
+    start: usize,
+    end: usize,
+    n: usize,
+    len: usize,
+}
+
+impl<T> IntoIter<T> {
+    pub(crate) fn new(
+        blocks: array_stack::IntoIter<Block<T>>,
+        len: usize,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            blocks,
+            front: Vec::new().into_iter(),
+            back: Vec::new().into_iter(),
+            start: 0,
+            end: capacity,
+            n: len,
+            len,
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => {
+                    let len = block.len();
+                    self.front = into_prefix(block, self.start, self.n).into_iter();
+                    self.start += len;
+                }
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => {
+                    self.end -= block.len();
+                    self.back = into_prefix(block, self.end, self.n).into_iter();
+                }
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Drops the elements still in blocks that were never reached.
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+impl<T> IntoIterator for RootishArrayStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let (n, capacity) = (self.n, self.allocated_slots());
+        self.n = 0;
+        IntoIter::new(mem::take(&mut self.blocks).into_iter(), n, capacity)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RootishArrayStack<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RootishArrayStack<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for RootishArrayStack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.insert_many(self.n, iter);
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for RootishArrayStack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut rootish_array_stack = Self::new();
+        rootish_array_stack.extend(iter);
+        rootish_array_stack
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -138,4 +758,143 @@ mod test {
         rootish_array_stack.add(0, 10);
         assert_eq!(rootish_array_stack.get(0), Some(&10));
     }
+
+    #[test]
+    fn rootish_array_stack_holds_move_only_values() {
+        let mut rootish_array_stack: RootishArrayStack<Box<dyn Fn() -> usize>> =
+            RootishArrayStack::new();
+        for i in 0..10 {
+            rootish_array_stack.add(0, Box::new(move || i));
+        }
+        // stack: [9, 8, ..., 0]
+        for i in 0..10 {
+            assert_eq!(rootish_array_stack.get(i).unwrap()(), 9 - i);
+        }
+        assert_eq!(rootish_array_stack.remove(4).unwrap()(), 5);
+        assert_eq!(rootish_array_stack.get(4).unwrap()(), 4);
+    }
+
+    #[test]
+    fn rootish_array_stack_iterators_work() {
+        let mut rootish_array_stack: RootishArrayStack<_> = (0..5).collect();
+        rootish_array_stack.extend(5..8);
+        rootish_array_stack.remove(7);
+        // stack: [0, 1, 2, 3, 4, 5, 6], blocks: [0], [1, 2], [3, 4, 5], [6, _, _, _]
+        assert_eq!(rootish_array_stack.iter().len(), 7);
+        assert_eq!(
+            rootish_array_stack.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            rootish_array_stack
+                .iter()
+                .rev()
+                .copied()
+                .collect::<Vec<_>>(),
+            [6, 5, 4, 3, 2, 1, 0]
+        );
+        let mut iter = rootish_array_stack.iter();
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.rev().copied().collect::<Vec<_>>(), [5, 4, 3, 2, 1]);
+        for x in &mut rootish_array_stack {
+            *x += 1;
+        }
+        assert_eq!(
+            rootish_array_stack.into_iter().rev().collect::<Vec<_>>(),
+            [7, 6, 5, 4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn rootish_array_stack_locates_exactly() {
+        for b in 0..2000 {
+            let first = b * (b + 1) / 2;
+            assert_eq!(RootishArrayStack::<()>::locate(first), (b, 0));
+            assert_eq!(RootishArrayStack::<()>::locate(first + b), (b, b));
+        }
+        let b = 4_000_000_000usize;
+        let first = b * (b + 1) / 2;
+        assert!(first > 1 << 53);
+        assert_eq!(RootishArrayStack::<()>::locate(first - 1), (b - 1, b - 1));
+        assert_eq!(RootishArrayStack::<()>::locate(first), (b, 0));
+        assert_eq!(RootishArrayStack::<()>::i2b(usize::MAX), 6_074_000_999);
+    }
+
+    #[test]
+    fn rootish_array_stack_shifts_across_blocks() {
+        let mut rootish_array_stack = RootishArrayStack::new();
+        let mut expected = Vec::new();
+        for k in 0..100 {
+            let i = (k * 37) % (expected.len() + 1);
+            rootish_array_stack.add(i, k);
+            expected.insert(i, k);
+        }
+        assert!(rootish_array_stack.iter().eq(expected.iter()));
+        *rootish_array_stack.get_mut(50).unwrap() = 1000;
+        expected[50] = 1000;
+        while !expected.is_empty() {
+            let i = (expected.len() * 37 / 3) % expected.len();
+            assert_eq!(rootish_array_stack.remove(i), Some(expected.remove(i)));
+            assert_eq!(rootish_array_stack.size(), expected.len());
+            assert!(rootish_array_stack.iter().eq(expected.iter()));
+        }
+        assert_eq!(rootish_array_stack.get_mut(0), None);
+    }
+
+    #[test]
+    fn rootish_array_stack_bulk_operations_work() {
+        let mut rootish_array_stack = (0..5).collect::<RootishArrayStack<_>>();
+        rootish_array_stack.insert_many(2, 10..20);
+        let mut expected = (0..5).collect::<Vec<_>>();
+        expected.splice(2..2, 10..20);
+        assert_eq!(
+            rootish_array_stack.iter().copied().collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            rootish_array_stack.drain(1..13).collect::<Vec<_>>(),
+            expected[1..13]
+        );
+        assert_eq!(
+            rootish_array_stack.iter().copied().collect::<Vec<_>>(),
+            [0, 3, 4]
+        );
+        rootish_array_stack.remove_range(..);
+        assert_eq!(rootish_array_stack.size(), 0);
+        rootish_array_stack.insert_many(0, vec![7, 8]);
+        assert_eq!(
+            rootish_array_stack.iter().copied().collect::<Vec<_>>(),
+            [7, 8]
+        );
+    }
+
+    #[test]
+    fn rootish_array_stack_drops_each_element_once() {
+        use std::rc::Rc;
+        let x = Rc::new(());
+        let mut rootish_array_stack = (0..10)
+            .map(|_| Rc::clone(&x))
+            .collect::<RootishArrayStack<_>>();
+        rootish_array_stack.add(3, Rc::clone(&x));
+        drop(rootish_array_stack.remove(7));
+        drop(rootish_array_stack.drain(2..4));
+        assert_eq!(Rc::strong_count(&x), 9);
+        let copy = rootish_array_stack.clone();
+        let mut iter = rootish_array_stack.into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&x), 9);
+        drop(copy);
+        assert_eq!(Rc::strong_count(&x), 1);
+        let rootish_array_stack = (0..4).collect::<RootishArrayStack<_>>();
+        assert_eq!(
+            format!("{:#?}", rootish_array_stack)
+                .split_whitespace()
+                .collect::<String>(),
+            "RootishArrayStack{blocks:[[Some(0,),],[Some(1,),Some(2,),],[Some(3,),None,None,],],n:4,}"
+        );
+    }
 }
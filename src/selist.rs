@@ -0,0 +1,776 @@
+use crate::{
+    bounded_deque::BoundedDeque,
+    error::Error,
+    format,
+    interfaces::{Deque, List, Queue},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    ptr::NonNull,
+};
+
+const DEFAULT_BLOCK_SIZE: usize = 8;
+
+/// The space-efficient linked list: a doubly linked list of blocks, each a
+/// bounded deque of capacity `b + 1`. Every block but the last holds between
+/// `b - 1` and `b + 1` elements, so locating index `i` walks past
+/// O(1 + min(i, n - i) / b) blocks and the list uses n + O(n / b) space.
+pub struct SEList<T> {
+    n: usize,
+    b: usize,
+    dummy: Link<T>,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = NonNull<Node<T>>;
+
+struct Node<T> {
+    d: BoundedDeque<T>,
+    next: Link<T>,
+    prev: Link<T>,
+}
+
+unsafe impl<T: Send> Send for SEList<T> {}
+unsafe impl<T: Sync> Sync for SEList<T> {}
+
+impl<T> SEList<T> {
+    pub fn new() -> Self {
+        Self::with_block_size(DEFAULT_BLOCK_SIZE)
+    }
+
+    /// Creates an empty list whose blocks hold about `b` elements.
+    ///
+    /// Panics if `b < 2`.
+    pub fn with_block_size(b: usize) -> Self {
+        assert!(b >= 2, "block size (is {}) should be >= 2", b);
+        let dummy = Node::new(0);
+        // SAFETY: `dummy` was just allocated and is not shared yet.
+        unsafe {
+            (*dummy.as_ptr()).next = dummy;
+            (*dummy.as_ptr()).prev = dummy;
+        }
+        Self {
+            n: 0,
+            b,
+            dummy,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.b
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        // SAFETY: `dummy` is always a live node.
+        unsafe {
+            Iter {
+                head: (*self.dummy.as_ptr()).next,
+                head_j: 0,
+                tail: self.dummy,
+                tail_j: 0,
+                len: self.n,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        // SAFETY: `dummy` is always a live node.
+        unsafe {
+            IterMut {
+                head: (*self.dummy.as_ptr()).next,
+                head_j: 0,
+                tail: self.dummy,
+                tail_j: 0,
+                len: self.n,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// Returns the block holding index `i` and the index within it, walking
+    /// from whichever end is closer. For `i == n` this is `(dummy, 0)`.
+    fn get_location(&self, mut i: usize) -> (Link<T>, usize) {
+        // SAFETY: every link reachable from `dummy` points to a live node.
+        unsafe {
+            if i < self.n / 2 {
+                let mut u = (*self.dummy.as_ptr()).next;
+                while i >= (*u.as_ptr()).d.size() {
+                    i -= (*u.as_ptr()).d.size();
+                    u = (*u.as_ptr()).next;
+                }
+                (u, i)
+            } else {
+                let mut u = self.dummy;
+                let mut idx = self.n;
+                while i < idx {
+                    u = (*u.as_ptr()).prev;
+                    idx -= (*u.as_ptr()).d.size();
+                }
+                (u, i - idx)
+            }
+        }
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        let (u, j) = self.get_location(i);
+        // SAFETY: `get_location(i)` is a live node of this list for `i < n`.
+        unsafe { (*u.as_ptr()).d.get(j) }
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
+        }
+        let (u, j) = self.get_location(i);
+        // SAFETY: `get_location(i)` is a live node of this list for `i < n`.
+        unsafe { (*u.as_ptr()).d.get_mut(j) }
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+
+    /// Inserts a new, empty block before `w` and returns it.
+    fn add_before(&mut self, w: Link<T>) -> Link<T> {
+        // SAFETY: `w` and its predecessor are live nodes of this list.
+        unsafe {
+            let u = Node::new(self.b + 1);
+            (*u.as_ptr()).prev = (*w.as_ptr()).prev;
+            (*u.as_ptr()).next = w;
+            (*w.as_ptr()).prev = u;
+            (*(*u.as_ptr()).prev.as_ptr()).next = u;
+            u
+        }
+    }
+
+    /// Inserts `x` at index `i`, shifting elements across at most `b` blocks.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        // SAFETY: every link reachable from `dummy` points to a live node.
+        unsafe {
+            if i == self.n {
+                let mut last = (*self.dummy.as_ptr()).prev;
+                if last == self.dummy || (*last.as_ptr()).d.is_full() {
+                    last = self.add_before(self.dummy);
+                }
+                (*last.as_ptr()).d.push_back(x);
+                self.n += 1;
+                return Ok(());
+            }
+            let (u, j) = self.get_location(i);
+            // Find a block with room within the next b; if there is none,
+            // spread the b full blocks over b + 1.
+            let mut v = u;
+            let mut r = 0;
+            while r < self.b && v != self.dummy && (*v.as_ptr()).d.is_full() {
+                v = (*v.as_ptr()).next;
+                r += 1;
+            }
+            if r == self.b {
+                self.spread(u);
+                v = u;
+            }
+            if v == self.dummy {
+                v = self.add_before(v);
+            }
+            // Shift one element from each block into the next, back to `u`.
+            while v != u {
+                let prev = (*v.as_ptr()).prev;
+                let y = (*prev.as_ptr()).d.pop_back().unwrap();
+                (*v.as_ptr()).d.add(0, y);
+                v = prev;
+            }
+            (*u.as_ptr()).d.add(j, x);
+        }
+        self.n += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        let (u, j) = self.get_location(i);
+        // SAFETY: every link reachable from `dummy` points to a live node.
+        let x = unsafe {
+            // Find a block with a spare element within the next b; if there
+            // is none, gather the b minimal blocks into b - 1.
+            let mut v = u;
+            let mut r = 0;
+            while r < self.b && v != self.dummy && (*v.as_ptr()).d.size() == self.b - 1 {
+                v = (*v.as_ptr()).next;
+                r += 1;
+            }
+            if r == self.b {
+                self.gather(u);
+            }
+            let x = (*u.as_ptr()).d.remove(j).unwrap();
+            // Pull one element from each block into the previous one until
+            // a block has one to spare.
+            v = u;
+            while (*v.as_ptr()).d.size() < self.b - 1 && (*v.as_ptr()).next != self.dummy {
+                let next = (*v.as_ptr()).next;
+                let y = (*next.as_ptr()).d.pop_front().unwrap();
+                (*v.as_ptr()).d.push_back(y);
+                v = next;
+            }
+            if (*v.as_ptr()).d.size() == 0 {
+                self.remove_node(v);
+            }
+            x
+        };
+        self.n -= 1;
+        Ok(x)
+    }
+
+    /// Turns the `b` full blocks starting at `u` into `b + 1` blocks of `b`.
+    fn spread(&mut self, u: Link<T>) {
+        // SAFETY: `u` and the `b` blocks after it are live nodes of this list.
+        unsafe {
+            let mut w = u;
+            for _ in 0..self.b {
+                w = (*w.as_ptr()).next;
+            }
+            w = self.add_before(w);
+            while w != u {
+                let prev = (*w.as_ptr()).prev;
+                while (*w.as_ptr()).d.size() < self.b {
+                    let y = (*prev.as_ptr()).d.pop_back().unwrap();
+                    (*w.as_ptr()).d.add(0, y);
+                }
+                w = prev;
+            }
+        }
+    }
+
+    /// Turns the `b` blocks of `b - 1` starting at `u` into `b - 1` blocks of `b`.
+    fn gather(&mut self, u: Link<T>) {
+        // SAFETY: `u` and the `b - 1` blocks after it are live nodes of this list.
+        unsafe {
+            let mut w = u;
+            for _ in 0..self.b - 1 {
+                let next = (*w.as_ptr()).next;
+                while (*w.as_ptr()).d.size() < self.b {
+                    let y = (*next.as_ptr()).d.pop_front().unwrap();
+                    (*w.as_ptr()).d.push_back(y);
+                }
+                w = next;
+            }
+            self.remove_node(w);
+        }
+    }
+
+    /// Unlinks and frees the block `w`, dropping any elements left in it.
+    fn remove_node(&mut self, w: Link<T>) {
+        // SAFETY: `w` is a live node of this list other than `dummy`; once
+        // unlinked it is owned here and freed.
+        unsafe {
+            (*(*w.as_ptr()).prev.as_ptr()).next = (*w.as_ptr()).next;
+            (*(*w.as_ptr()).next.as_ptr()).prev = (*w.as_ptr()).prev;
+            drop(Box::from_raw(w.as_ptr()));
+        }
+    }
+
+    fn blocks(&self) -> Vec<&BoundedDeque<T>> {
+        let mut blocks = Vec::new();
+        // SAFETY: every link reachable from `dummy` points to a live node.
+        unsafe {
+            let mut u = (*self.dummy.as_ptr()).next;
+            while u != self.dummy {
+                blocks.push(&(*u.as_ptr()).d);
+                u = (*u.as_ptr()).next;
+            }
+        }
+        blocks
+    }
+}
+
+impl<T> Node<T> {
+    fn new(capacity: usize) -> Link<T> {
+        let u = Box::new(Self {
+            d: BoundedDeque::new(capacity),
+            next: NonNull::dangling(),
+            prev: NonNull::dangling(),
+        });
+        NonNull::from(Box::leak(u))
+    }
+}
+
+impl<T> Drop for SEList<T> {
+    fn drop(&mut self) {
+        // SAFETY: `dummy` is always a live node.
+        while unsafe { (*self.dummy.as_ptr()).next } != self.dummy {
+            self.remove_node(unsafe { (*self.dummy.as_ptr()).next });
+        }
+        // SAFETY: `dummy` was allocated by `Node::new` and holds no elements.
+        unsafe { drop(Box::from_raw(self.dummy.as_ptr())) }
+    }
+}
+
+impl<T> Default for SEList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for SEList<T> {
+    fn clone(&self) -> Self {
+        let mut selist = Self::with_block_size(self.b);
+        selist.extend(self.iter().cloned());
+        selist
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for SEList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("SEList")
+                .field("b", &self.b)
+                .field("blocks", &self.blocks())
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SEList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SEList<T> {}
+
+impl<T: PartialOrd> PartialOrd for SEList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for SEList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for SEList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for SEList<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for SEList<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SEList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for SEList<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<SEList<T>> for Vec<T> {
+    fn from(xs: SEList<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for SEList<T> {
+    fn size(&self) -> usize {
+        SEList::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        SEList::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        SEList::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        SEList::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        SEList::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        SEList::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        SEList::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        SEList::try_remove(self, i)
+    }
+}
+
+impl<T> Queue<T> for SEList<T> {
+    fn push_back(&mut self, x: T) {
+        SEList::add(self, SEList::size(self), x)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        SEList::remove(self, 0)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        SEList::get(self, 0)
+    }
+}
+
+impl<T> Deque<T> for SEList<T> {
+    fn push_front(&mut self, x: T) {
+        SEList::add(self, 0, x)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        SEList::remove(self, SEList::size(self).checked_sub(1)?)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        SEList::get(self, SEList::size(self).checked_sub(1)?)
+    }
+}
+
+impl<T> MemoryUsage for SEList<T> {
+    fn heap_bytes(&self) -> usize {
+        let r = self.blocks().len();
+        (r + 1) * std::mem::size_of::<Node<T>>() + self.allocated_slots() * std::mem::size_of::<T>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.blocks().len() * (self.b + 1)
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.allocated_slots() - self.n
+    }
+}
+
+/// Yields from block `head` at index `head_j` forwards and from block `tail`
+/// below index `tail_j` backwards, stopping after `len` elements.
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    head_j: usize,
+    tail: Link<T>,
+    tail_j: usize,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `head` is a live node holding some of the `len` elements
+        // not yet yielded from either end, or precedes one that does.
+        unsafe {
+            while self.head_j == (*self.head.as_ptr()).d.size() {
+                self.head = (*self.head.as_ptr()).next;
+                self.head_j = 0;
+            }
+            self.head_j += 1;
+            (*self.head.as_ptr()).d.get(self.head_j - 1)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `tail` is a live node holding some of the `len` elements
+        // not yet yielded from either end, or follows one that does.
+        unsafe {
+            while self.tail_j == 0 {
+                self.tail = (*self.tail.as_ptr()).prev;
+                self.tail_j = (*self.tail.as_ptr()).d.size();
+            }
+            self.tail_j -= 1;
+            (*self.tail.as_ptr()).d.get(self.tail_j)
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    head_j: usize,
+    tail: Link<T>,
+    tail_j: usize,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: as for `Iter`; each element is yielded at most once.
+        unsafe {
+            while self.head_j == (*self.head.as_ptr()).d.size() {
+                self.head = (*self.head.as_ptr()).next;
+                self.head_j = 0;
+            }
+            self.head_j += 1;
+            (*self.head.as_ptr()).d.get_mut(self.head_j - 1)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: as for `Iter`; each element is yielded at most once.
+        unsafe {
+            while self.tail_j == 0 {
+                self.tail = (*self.tail.as_ptr()).prev;
+                self.tail_j = (*self.tail.as_ptr()).d.size();
+            }
+            self.tail_j -= 1;
+            (*self.tail.as_ptr()).d.get_mut(self.tail_j)
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    list: SEList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.remove(0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.n, Some(self.list.n))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.remove(self.list.n.checked_sub(1)?)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for SEList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SEList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SEList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for SEList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for SEList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut selist = Self::new();
+        selist.extend(iter);
+        selist
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn block_sizes<T>(selist: &SEList<T>) -> Vec<usize> {
+        selist.blocks().iter().map(|d| d.size()).collect()
+    }
+
+    #[test]
+    fn selist_works() {
+        let mut selist = SEList::with_block_size(3);
+        let mut expected = Vec::new();
+        for i in 0..200 {
+            let k = (i * 7) % (expected.len() + 1);
+            selist.add(k, i);
+            expected.insert(k, i);
+            let sizes = block_sizes(&selist);
+            assert!(sizes[..sizes.len() - 1]
+                .iter()
+                .all(|&s| (2..=4).contains(&s)));
+        }
+        for (i, x) in expected.iter().enumerate() {
+            assert_eq!(selist.get(i), Some(x));
+        }
+        assert_eq!(selist.set(3, 1000), Some(expected[3]));
+        expected[3] = 1000;
+        while !expected.is_empty() {
+            let k = (expected.len() * 5) % (expected.len() + 3) % expected.len();
+            assert_eq!(selist.remove(k), Some(expected.remove(k)));
+            let sizes = block_sizes(&selist);
+            assert!(sizes.iter().all(|&s| s > 0));
+            assert!(sizes.iter().rev().skip(1).all(|&s| (2..=4).contains(&s)));
+        }
+        assert_eq!(selist.remove(0), None);
+        assert!(block_sizes(&selist).is_empty());
+    }
+
+    #[test]
+    fn selist_spreads_and_gathers() {
+        let mut selist = SEList::with_block_size(3);
+        selist.extend(0..12);
+        assert_eq!(block_sizes(&selist), [4, 4, 4]);
+        // no room in the next b blocks: spread them over b + 1
+        selist.add(1, -1);
+        assert_eq!(block_sizes(&selist), [4, 3, 3, 3]);
+        selist.remove(0);
+        selist.remove(0);
+        selist.remove(3);
+        selist.remove(5);
+        // blocks: [1 2] [3 5] [6 8] [9 10 11]
+        assert_eq!(block_sizes(&selist), [2, 2, 2, 3]);
+        // no spare element in the next b blocks: gather them into b - 1
+        assert_eq!(selist.remove(0), Some(1));
+        assert_eq!(block_sizes(&selist), [2, 3, 3]);
+        assert_eq!(
+            selist.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 5, 6, 8, 9, 10, 11]
+        );
+    }
+
+    #[test]
+    fn selist_iterators_work() {
+        let mut selist = SEList::with_block_size(2);
+        selist.extend(0..10);
+        for x in selist.iter_mut() {
+            *x *= 2;
+        }
+        let mut iter = selist.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&18));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(
+            iter.copied().collect::<Vec<_>>(),
+            [2, 4, 6, 8, 10, 12, 14, 16]
+        );
+        let mut into_iter = selist.into_iter();
+        assert_eq!(into_iter.next_back(), Some(18));
+        assert_eq!(
+            into_iter.collect::<Vec<_>>(),
+            [0, 2, 4, 6, 8, 10, 12, 14, 16]
+        );
+    }
+}
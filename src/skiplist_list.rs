@@ -0,0 +1,881 @@
+use crate::{error::Error, format, interfaces::List, memory::MemoryUsage, rng::Rng};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Index, IndexMut, Range, RangeBounds},
+    ptr::NonNull,
+};
+
+/// A list stored as a skiplist whose edges record how many positions they
+/// span, so `get`, `set`, `add` and `remove` find index `i` in expected
+/// O(log n) steps. Counting the sentinel as position 0 and the element at
+/// index `i` as position `i + 1`, the edge at level `r` from `u` spans
+/// `length[r]` positions; an edge to nothing spans to position `n + 1`.
+pub struct SkiplistList<T> {
+    n: usize,
+    h: usize,
+    sentinel: NonNull<Node<T>>,
+    rng: Rng,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    x: MaybeUninit<T>,
+    next: Vec<Link<T>>,
+    length: Vec<usize>,
+}
+
+unsafe impl<T: Send> Send for SkiplistList<T> {}
+unsafe impl<T: Sync> Sync for SkiplistList<T> {}
+
+impl<T> SkiplistList<T> {
+    /// Creates an empty list whose tower heights are drawn from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_rng(Rng::from_entropy())
+    }
+
+    /// Creates an empty list whose tower heights are drawn from a generator
+    /// seeded with `seed`, so the same operations build the same skiplist.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(Rng::new(seed))
+    }
+
+    fn with_rng(rng: Rng) -> Self {
+        Self {
+            n: 0,
+            h: 0,
+            sentinel: Node::new(MaybeUninit::uninit(), 0, 1),
+            rng,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        // SAFETY: `sentinel` is always a live node.
+        unsafe {
+            Iter {
+                sentinel: self.sentinel,
+                h: self.h,
+                u: (&*self.sentinel.as_ptr()).next[0],
+                i: 0,
+                len: self.n,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        // SAFETY: `sentinel` is always a live node.
+        unsafe {
+            IterMut {
+                sentinel: self.sentinel,
+                h: self.h,
+                u: (&*self.sentinel.as_ptr()).next[0],
+                i: 0,
+                len: self.n,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// Follows level `r` from node `u` at position `j` while the next node
+    /// is at position `i` or before, and returns the last node reached.
+    ///
+    /// Only the links of each node are borrowed, so this is safe to call
+    /// while an `IterMut` has handed out its elements.
+    ///
+    /// # Safety
+    /// `u` must be a live node of this list with at least `r + 1` levels.
+    unsafe fn advance(
+        mut u: NonNull<Node<T>>,
+        mut j: usize,
+        r: usize,
+        i: usize,
+    ) -> (NonNull<Node<T>>, usize) {
+        loop {
+            let next = &(*u.as_ptr()).next;
+            let length = &(*u.as_ptr()).length;
+            match next[r] {
+                Some(w) if length[r] <= i - j => {
+                    j += length[r];
+                    u = w;
+                }
+                _ => return (u, j),
+            }
+        }
+    }
+
+    /// Returns the node at position `i`, the predecessor of the element at index `i`.
+    fn find_pred(&self, i: usize) -> NonNull<Node<T>> {
+        // SAFETY: `sentinel` is the live sentinel of this list, of height `h`.
+        unsafe { Self::find_pred_from(self.sentinel, self.h, i) }
+    }
+
+    /// `find_pred` for the list with this `sentinel` and height `h`.
+    ///
+    /// # Safety
+    /// `sentinel` must be the live sentinel of a list of height `h`, and `i`
+    /// at most its size.
+    unsafe fn find_pred_from(sentinel: NonNull<Node<T>>, h: usize, i: usize) -> NonNull<Node<T>> {
+        let mut u = sentinel;
+        let mut j = 0;
+        for r in (0..=h).rev() {
+            (u, j) = Self::advance(u, j, r, i);
+        }
+        u
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the successor of `find_pred(i)` is the initialized node at index `i`.
+        unsafe {
+            let w = (&*self.find_pred(i).as_ptr()).next[0]?;
+            Some((*w.as_ptr()).x.assume_init_ref())
+        }
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: the successor of `find_pred(i)` is the initialized node at index `i`.
+        unsafe {
+            let w = (&*self.find_pred(i).as_ptr()).next[0]?;
+            Some((*w.as_ptr()).x.assume_init_mut())
+        }
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+
+    /// Inserts `x` at index `i` in expected O(log n) time.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        let k = self.rng.next_geometric();
+        let w = Node::new(MaybeUninit::new(x), k, 0);
+        self.grow_to(k);
+        let mut u = self.sentinel;
+        let mut j = 0;
+        // SAFETY: every link reachable from `sentinel` points to a live node,
+        // and `w` is a new node with `k + 1` levels.
+        unsafe {
+            for r in (0..=self.h).rev() {
+                (u, j) = Self::advance(u, j, r, i);
+                // The edge from `u` spans position i + 1, where `w` goes.
+                let u_node = &mut *u.as_ptr();
+                u_node.length[r] += 1;
+                if r <= k {
+                    let w_node = &mut *w.as_ptr();
+                    w_node.next[r] = u_node.next[r];
+                    w_node.length[r] = u_node.length[r] - (i + 1 - j);
+                    u_node.next[r] = Some(w);
+                    u_node.length[r] = i + 1 - j;
+                }
+            }
+        }
+        self.n += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        let mut u = self.sentinel;
+        let mut j = 0;
+        let mut removed = None;
+        // SAFETY: every link reachable from `sentinel` points to a live node;
+        // the node at index `i` is unlinked from every level before it is freed.
+        let x = unsafe {
+            for r in (0..=self.h).rev() {
+                (u, j) = Self::advance(u, j, r, i);
+                let u_node = &mut *u.as_ptr();
+                if let Some(w) = u_node.next[r] {
+                    if j + u_node.length[r] == i + 1 {
+                        u_node.length[r] += (&*w.as_ptr()).length[r];
+                        u_node.next[r] = (&*w.as_ptr()).next[r];
+                        removed = Some(w);
+                    }
+                }
+                u_node.length[r] -= 1;
+            }
+            Box::from_raw(removed.unwrap().as_ptr()).x.assume_init()
+        };
+        self.n -= 1;
+        self.shrink_height();
+        Ok(x)
+    }
+
+    /// Inserts the elements of `iter` at index `i`, splitting the list there
+    /// once and taking expected O(log n) time per element.
+    ///
+    /// Panics if `i > size()`.
+    pub fn insert_many<I: IntoIterator<Item = T>>(&mut self, i: usize, iter: I) {
+        if let Err(e) = Error::check_insert(i, self.n) {
+            panic!("{}", e);
+        }
+        let mut rest = self.truncate(i);
+        self.extend(iter);
+        self.absorb(&mut rest);
+    }
+
+    /// Removes the elements in `range`.
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.drain(range);
+    }
+
+    /// Removes the elements in `range` and returns them in order. Cutting
+    /// them out takes expected O(log n) time.
+    ///
+    /// The elements are removed immediately, even if the iterator is not consumed.
+    /// Panics if `range` is out of bounds.
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<T> {
+        let Range { start, end } =
+            Error::check_range(range, self.n).unwrap_or_else(|e| panic!("{}", e));
+        let mut drained = self.truncate(start);
+        let mut rest = drained.truncate(end - start);
+        self.absorb(&mut rest);
+        Drain {
+            inner: drained.into_iter(),
+        }
+    }
+
+    /// Moves every element of `other` to the end of this list in expected
+    /// O(log n) time, leaving `other` empty.
+    pub fn absorb(&mut self, other: &mut SkiplistList<T>) {
+        self.grow_to(other.h);
+        let mut u = self.sentinel;
+        let mut j = 0;
+        // SAFETY: both lists' links point to live nodes; each level of
+        // `other` is relinked after the last node of that level here, and
+        // `other` is left with no links to them.
+        unsafe {
+            let o = &mut *other.sentinel.as_ptr();
+            for r in (0..=self.h).rev() {
+                (u, j) = Self::advance(u, j, r, usize::MAX);
+                let u_node = &mut *u.as_ptr();
+                if r <= other.h {
+                    u_node.next[r] = o.next[r];
+                    u_node.length[r] = self.n + o.length[r] - j;
+                } else {
+                    u_node.length[r] += other.n;
+                }
+            }
+            o.next = vec![None];
+            o.length = vec![1];
+        }
+        self.n += other.n;
+        other.n = 0;
+        other.h = 0;
+    }
+
+    /// Truncates the list to its first `i` elements and returns the rest, in
+    /// expected O(log n) time.
+    ///
+    /// Panics if `i > size()`.
+    pub fn truncate(&mut self, i: usize) -> SkiplistList<T> {
+        assert!(
+            i <= self.n,
+            "truncate index (is {}) should be <= len (is {})",
+            i,
+            self.n
+        );
+        let mut other = SkiplistList::with_rng(Rng::new(self.rng.next_u64()));
+        other.grow_to(self.h);
+        let mut u = self.sentinel;
+        let mut j = 0;
+        // SAFETY: every link reachable from `sentinel` points to a live node;
+        // each level is cut after position `i` and its tail handed to `other`.
+        unsafe {
+            let o = &mut *other.sentinel.as_ptr();
+            for r in (0..=self.h).rev() {
+                (u, j) = Self::advance(u, j, r, i);
+                let u_node = &mut *u.as_ptr();
+                o.next[r] = u_node.next[r].take();
+                o.length[r] = j + u_node.length[r] - i;
+                u_node.length[r] = i + 1 - j;
+            }
+        }
+        other.n = self.n - i;
+        self.n = i;
+        self.shrink_height();
+        other.shrink_height();
+        other
+    }
+
+    /// Raises the sentinel's tower to `k + 1` levels.
+    fn grow_to(&mut self, k: usize) {
+        // SAFETY: `sentinel` is always a live node.
+        let s = unsafe { &mut *self.sentinel.as_ptr() };
+        while self.h < k {
+            s.next.push(None);
+            s.length.push(self.n + 1);
+            self.h += 1;
+        }
+    }
+
+    /// Lowers the sentinel's tower to the tallest tower in the list.
+    fn shrink_height(&mut self) {
+        // SAFETY: `sentinel` is always a live node.
+        let s = unsafe { &mut *self.sentinel.as_ptr() };
+        while self.h > 0 && s.next[self.h].is_none() {
+            s.next.pop();
+            s.length.pop();
+            self.h -= 1;
+        }
+    }
+}
+
+impl<T> Node<T> {
+    /// Allocates a node with `k + 1` levels, each an edge to nothing spanning `length`.
+    fn new(x: MaybeUninit<T>, k: usize, length: usize) -> NonNull<Node<T>> {
+        let u = Box::new(Self {
+            x,
+            next: vec![None; k + 1],
+            length: vec![length; k + 1],
+        });
+        NonNull::from(Box::leak(u))
+    }
+}
+
+impl<T> Drop for SkiplistList<T> {
+    fn drop(&mut self) {
+        // SAFETY: level 0 links every node once; each is freed after its
+        // successor is read, and only element nodes hold an initialized value.
+        unsafe {
+            let sentinel = Box::from_raw(self.sentinel.as_ptr());
+            let mut u = sentinel.next[0];
+            while let Some(w) = u {
+                let mut node = Box::from_raw(w.as_ptr());
+                u = node.next[0];
+                node.x.assume_init_drop();
+            }
+        }
+    }
+}
+
+impl<T> Default for SkiplistList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for SkiplistList<T> {
+    fn clone(&self) -> Self {
+        let mut skiplist_list = Self::with_rng(self.rng.clone());
+        skiplist_list.extend(self.iter().cloned());
+        skiplist_list
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows each level of the skiplist instead.
+impl<T: fmt::Debug> fmt::Debug for SkiplistList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            // SAFETY: every link reachable from `sentinel` points to a live,
+            // initialized element node.
+            let levels = (0..=self.h)
+                .map(|r| unsafe {
+                    let mut level = Vec::new();
+                    let mut u = (&*self.sentinel.as_ptr()).next[r];
+                    while let Some(w) = u {
+                        level.push((*w.as_ptr()).x.assume_init_ref());
+                        u = (&*w.as_ptr()).next[r];
+                    }
+                    level
+                })
+                .collect::<Vec<_>>();
+            f.debug_struct("SkiplistList")
+                .field("levels", &levels)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SkiplistList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SkiplistList<T> {}
+
+impl<T: PartialOrd> PartialOrd for SkiplistList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for SkiplistList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for SkiplistList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for SkiplistList<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for SkiplistList<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SkiplistList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for SkiplistList<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<SkiplistList<T>> for Vec<T> {
+    fn from(xs: SkiplistList<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for SkiplistList<T> {
+    fn size(&self) -> usize {
+        SkiplistList::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        SkiplistList::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        SkiplistList::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        SkiplistList::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        SkiplistList::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        SkiplistList::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        SkiplistList::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        SkiplistList::try_remove(self, i)
+    }
+}
+
+impl<T> MemoryUsage for SkiplistList<T> {
+    fn heap_bytes(&self) -> usize {
+        let mut bytes = 0;
+        let mut u = Some(self.sentinel);
+        // SAFETY: every link reachable from `sentinel` points to a live node.
+        unsafe {
+            while let Some(w) = u {
+                let node = &*w.as_ptr();
+                bytes += std::mem::size_of::<Node<T>>()
+                    + node.next.capacity() * std::mem::size_of::<Link<T>>()
+                    + node.length.capacity() * std::mem::size_of::<usize>();
+                u = node.next[0];
+            }
+        }
+        bytes
+    }
+
+    fn allocated_slots(&self) -> usize {
+        // `sentinel` has a slot too.
+        self.n + 1
+    }
+
+    fn wasted_slots(&self) -> usize {
+        1
+    }
+}
+
+/// Yields the elements in order by following level 0 from the front, and
+/// by searching from the sentinel for each element taken from the back.
+/// Stepping forward is O(1), but each step back is an O(log n) expected
+/// search, so iterating in reverse takes O(n log n).
+pub struct Iter<'a, T> {
+    sentinel: NonNull<Node<T>>,
+    h: usize,
+    /// The node at index `i`, the next one from the front.
+    u: Link<T>,
+    i: usize,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let w = self.u?;
+        self.len -= 1;
+        self.i += 1;
+        // SAFETY: `w` is an initialized node of the list this iterator borrows.
+        unsafe {
+            self.u = (&(*w.as_ptr()).next)[0];
+            Some((*w.as_ptr()).x.assume_init_ref())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    /// Searches for the last element not yet yielded, in O(log n) expected time.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the list is borrowed, so `sentinel` and `h` are current,
+        // and index `i + len` is one of the elements not yet yielded.
+        unsafe {
+            let u = SkiplistList::find_pred_from(self.sentinel, self.h, self.i + self.len);
+            let w = (&(*u.as_ptr()).next)[0]?;
+            Some((*w.as_ptr()).x.assume_init_ref())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Mutable [`Iter`], with the same costs: O(1) per step forward and
+/// O(log n) expected per step back.
+pub struct IterMut<'a, T> {
+    sentinel: NonNull<Node<T>>,
+    h: usize,
+    u: Link<T>,
+    i: usize,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let w = self.u?;
+        self.len -= 1;
+        self.i += 1;
+        // SAFETY: `w` is an initialized node of the list this iterator
+        // borrows mutably, and each node is yielded once.
+        unsafe {
+            self.u = (&(*w.as_ptr()).next)[0];
+            Some((*w.as_ptr()).x.assume_init_mut())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    /// Searches for the last element not yet yielded, in O(log n) expected time.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the list is borrowed mutably, so `sentinel` and `h` are
+        // current; the search only reads links, never the values already
+        // handed out, and index `i + len` has not been yielded yet.
+        unsafe {
+            let u = SkiplistList::find_pred_from(self.sentinel, self.h, self.i + self.len);
+            let w = (&(*u.as_ptr()).next)[0]?;
+            Some((*w.as_ptr()).x.assume_init_mut())
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    list: SkiplistList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.remove(0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.n, Some(self.list.n))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.remove(self.list.n.checked_sub(1)?)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// The elements removed by [`SkiplistList::drain`], cut out as a list of their own.
+pub struct Drain<T> {
+    inner: IntoIter<T>,
+}
+
+impl<T> Iterator for Drain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<T> {}
+
+impl<T> IntoIterator for SkiplistList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkiplistList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SkiplistList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for SkiplistList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for SkiplistList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut skiplist_list = Self::new();
+        skiplist_list.extend(iter);
+        skiplist_list
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_matches(skiplist_list: &SkiplistList<i32>, expected: &[i32]) {
+        assert_eq!(skiplist_list.size(), expected.len());
+        for (i, x) in expected.iter().enumerate() {
+            assert_eq!(skiplist_list.get(i), Some(x));
+        }
+        assert!(skiplist_list.iter().eq(expected.iter()));
+        assert!(skiplist_list.iter().rev().eq(expected.iter().rev()));
+    }
+
+    #[test]
+    fn skiplist_list_works() {
+        let mut skiplist_list = SkiplistList::with_seed(3);
+        let mut expected = Vec::new();
+        for i in 0..300 {
+            let k = (i as usize * 7) % (expected.len() + 1);
+            skiplist_list.add(k, i);
+            expected.insert(k, i);
+        }
+        assert_matches(&skiplist_list, &expected);
+        assert_eq!(skiplist_list.set(10, -1), Some(expected[10]));
+        expected[10] = -1;
+        for x in skiplist_list.iter_mut() {
+            *x += 1;
+        }
+        for x in expected.iter_mut() {
+            *x += 1;
+        }
+        while expected.len() > 100 {
+            let k = (expected.len() * 5) % (expected.len() + 3) % expected.len();
+            assert_eq!(skiplist_list.remove(k), Some(expected.remove(k)));
+        }
+        assert_matches(&skiplist_list, &expected);
+        assert_eq!(skiplist_list.remove(100), None);
+    }
+
+    #[test]
+    fn skiplist_list_truncates_and_absorbs() {
+        let mut skiplist_list = SkiplistList::with_seed(11);
+        skiplist_list.extend(0..100);
+        let mut tail = skiplist_list.truncate(60);
+        assert_matches(&skiplist_list, &(0..60).collect::<Vec<_>>());
+        assert_matches(&tail, &(60..100).collect::<Vec<_>>());
+        // both halves keep working after the split
+        tail.add(0, -1);
+        skiplist_list.add(60, -2);
+        assert_eq!(skiplist_list.truncate(61).size(), 0);
+        let mut rest = tail.truncate(20);
+        skiplist_list.absorb(&mut tail);
+        assert_eq!(tail.size(), 0);
+        tail.add(0, 7);
+        assert_matches(&tail, &[7]);
+        let mut expected = (0..60).chain(Some(-2)).chain(Some(-1)).collect::<Vec<_>>();
+        expected.extend(60..79);
+        assert_matches(&skiplist_list, &expected);
+        skiplist_list.absorb(&mut rest);
+        expected.extend(79..100);
+        assert_matches(&skiplist_list, &expected);
+        skiplist_list.add(81, 0);
+        expected.insert(81, 0);
+        assert_eq!(skiplist_list.remove(99), Some(expected.remove(99)));
+        assert_matches(&skiplist_list, &expected);
+        let mut empty = SkiplistList::new();
+        empty.absorb(&mut skiplist_list);
+        assert_matches(&empty, &expected);
+        assert_eq!(empty.truncate(0).size(), expected.len());
+    }
+
+    #[test]
+    fn skiplist_list_iterates_from_both_ends() {
+        let mut skiplist_list = SkiplistList::with_seed(5);
+        skiplist_list.extend(0..50);
+        let mut iter = skiplist_list.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&49));
+        assert_eq!(iter.next_back(), Some(&48));
+        assert_eq!(iter.len(), 47);
+        assert!(iter.eq((1..48).collect::<Vec<_>>().iter()));
+        let mut iter_mut = skiplist_list.iter_mut();
+        let first = iter_mut.next().unwrap();
+        let last = iter_mut.next_back().unwrap();
+        *first += 100;
+        *last += 100;
+        for x in iter_mut.rev().take(2) {
+            *x = -*x;
+        }
+        assert_eq!(skiplist_list.get(0), Some(&100));
+        assert_eq!(skiplist_list.get(49), Some(&149));
+        assert_eq!(skiplist_list.get(48), Some(&-48));
+        assert_eq!(skiplist_list.get(47), Some(&-47));
+        assert_eq!(skiplist_list.get(46), Some(&46));
+    }
+
+    #[test]
+    fn skiplist_list_bulk_operations_work() {
+        let mut skiplist_list = SkiplistList::with_seed(17);
+        let mut expected = (0..80).collect::<Vec<_>>();
+        skiplist_list.extend(expected.iter().copied());
+        skiplist_list.insert_many(30, 100..110);
+        expected.splice(30..30, 100..110);
+        assert_matches(&skiplist_list, &expected);
+        skiplist_list.insert_many(skiplist_list.size(), vec![-1, -2]);
+        expected.extend(vec![-1, -2]);
+        assert_matches(&skiplist_list, &expected);
+        let drained = skiplist_list.drain(25..45);
+        assert_eq!(drained.len(), 20);
+        assert!(drained.eq(expected.drain(25..45)));
+        assert_matches(&skiplist_list, &expected);
+        assert!(skiplist_list.drain(..3).rev().eq(expected.drain(..3).rev()));
+        skiplist_list.remove_range(60..);
+        expected.truncate(60);
+        assert_matches(&skiplist_list, &expected);
+        skiplist_list.remove_range(..);
+        assert_eq!(skiplist_list.size(), 0);
+        skiplist_list.insert_many(0, 0..3);
+        assert_matches(&skiplist_list, &[0, 1, 2]);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{format, interfaces::SSet, memory::MemoryUsage, rng::Rng};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A sorted set stored as a skiplist. Each element sits in a tower whose
+/// height `k` is chosen with probability 2^-(k+1), and level `r` links the
+/// towers of height at least `r` in order, so searches take expected
+/// O(log n) steps. Nodes live in an arena and link to each other by index;
+/// node 0 is the sentinel, whose tower is as tall as the tallest.
+#[derive(Clone)]
+pub struct SkiplistSSet<T> {
+    nodes: Vec<Node<T>>,
+    free: Vec<usize>,
+    h: usize,
+    n: usize,
+    rng: Rng,
+}
+
+#[derive(Clone)]
+struct Node<T> {
+    x: Option<T>,
+    next: Vec<Option<usize>>,
+}
+
+const SENTINEL: usize = 0;
+
+impl<T: Ord> SkiplistSSet<T> {
+    /// Creates an empty set whose tower heights are drawn from a randomly seeded generator.
+    pub fn new() -> Self {
+        Self::with_rng(Rng::from_entropy())
+    }
+
+    /// Creates an empty set whose tower heights are drawn from a generator
+    /// seeded with `seed`, so the same operations build the same skiplist.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(Rng::new(seed))
+    }
+
+    fn with_rng(rng: Rng) -> Self {
+        Self {
+            nodes: vec![Node {
+                x: None,
+                next: vec![None],
+            }],
+            free: Vec::new(),
+            h: 0,
+            n: 0,
+            rng,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            u: self.nodes[SENTINEL].next[0],
+            len: self.n,
+        }
+    }
+
+    fn value(&self, u: usize) -> &T {
+        self.nodes[u].x.as_ref().unwrap()
+    }
+
+    /// Follows level `r` from `u` past every element less than `x`.
+    fn advance(&self, mut u: usize, r: usize, x: &T) -> usize {
+        while let Some(w) = self.nodes[u].next[r] {
+            if self.value(w) >= x {
+                break;
+            }
+            u = w;
+        }
+        u
+    }
+
+    /// Returns the node after which `x` belongs: its predecessor in the set.
+    fn find_pred_node(&self, x: &T) -> usize {
+        let mut u = SENTINEL;
+        for r in (0..=self.h).rev() {
+            u = self.advance(u, r, x);
+        }
+        u
+    }
+
+    /// Returns the smallest element that is at least `x`, or `None` if there is none.
+    pub fn find(&self, x: &T) -> Option<&T> {
+        let u = self.find_pred_node(x);
+        Some(self.value(self.nodes[u].next[0]?))
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.find(x) == Some(x)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        Some(self.value(self.nodes[SENTINEL].next[0]?))
+    }
+
+    /// Adds `x` unless an equal element is present; returns whether it was added.
+    pub fn add(&mut self, x: T) -> bool {
+        let mut stack = vec![SENTINEL; self.h + 1];
+        let mut u = SENTINEL;
+        for r in (0..=self.h).rev() {
+            u = self.advance(u, r, &x);
+            if let Some(w) = self.nodes[u].next[r] {
+                if *self.value(w) == x {
+                    return false;
+                }
+            }
+            stack[r] = u;
+        }
+        let k = self.rng.next_geometric();
+        while self.h < k {
+            self.h += 1;
+            self.nodes[SENTINEL].next.push(None);
+            stack.push(SENTINEL);
+        }
+        let next = (0..=k).map(|r| self.nodes[stack[r]].next[r]).collect();
+        let node = Node { x: Some(x), next };
+        let w = match self.free.pop() {
+            Some(w) => {
+                self.nodes[w] = node;
+                w
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (r, &u) in stack.iter().enumerate().take(k + 1) {
+            self.nodes[u].next[r] = Some(w);
+        }
+        self.n += 1;
+        true
+    }
+
+    /// Removes and returns the element equal to `x`, or `None` if there is none.
+    pub fn remove(&mut self, x: &T) -> Option<T> {
+        let mut removed = None;
+        let mut u = SENTINEL;
+        for r in (0..=self.h).rev() {
+            u = self.advance(u, r, x);
+            if let Some(w) = self.nodes[u].next[r] {
+                if self.value(w) == x {
+                    self.nodes[u].next[r] = self.nodes[w].next[r];
+                    removed = Some(w);
+                }
+            }
+        }
+        let w = removed?;
+        while self.h > 0 && self.nodes[SENTINEL].next[self.h].is_none() {
+            self.nodes[SENTINEL].next.pop();
+            self.h -= 1;
+        }
+        let node = std::mem::replace(
+            &mut self.nodes[w],
+            Node {
+                x: None,
+                next: Vec::new(),
+            },
+        );
+        self.free.push(w);
+        self.n -= 1;
+        node.x
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[SENTINEL].next = vec![None];
+        self.free.clear();
+        self.h = 0;
+        self.n = 0;
+    }
+}
+
+impl<T: Ord> Default for SkiplistSSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the set of elements; `{:#?}` shows each level of the skiplist instead.
+impl<T: Ord + fmt::Debug> fmt::Debug for SkiplistSSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let levels = (0..=self.h)
+                .map(|r| {
+                    let mut level = Vec::new();
+                    let mut u = self.nodes[SENTINEL].next[r];
+                    while let Some(w) = u {
+                        level.push(self.value(w));
+                        u = self.nodes[w].next[r];
+                    }
+                    level
+                })
+                .collect::<Vec<_>>();
+            f.debug_struct("SkiplistSSet")
+                .field("levels", &levels)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: Ord> PartialEq for SkiplistSSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Ord> Eq for SkiplistSSet<T> {}
+
+impl<T: Ord> PartialOrd for SkiplistSSet<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for SkiplistSSet<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Ord + Hash> Hash for SkiplistSSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T: Ord + fmt::Display> fmt::Display for SkiplistSSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_set(f, self.iter())
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for SkiplistSSet<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T: Ord> From<SkiplistSSet<T>> for Vec<T> {
+    fn from(xs: SkiplistSSet<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T: Ord> SSet<T> for SkiplistSSet<T> {
+    fn size(&self) -> usize {
+        SkiplistSSet::size(self)
+    }
+
+    fn add(&mut self, x: T) -> bool {
+        SkiplistSSet::add(self, x)
+    }
+
+    fn remove(&mut self, x: &T) -> Option<T> {
+        SkiplistSSet::remove(self, x)
+    }
+
+    fn find(&self, x: &T) -> Option<&T> {
+        SkiplistSSet::find(self, x)
+    }
+}
+
+impl<T> MemoryUsage for SkiplistSSet<T> {
+    fn heap_bytes(&self) -> usize {
+        let links = self.nodes.iter().map(|u| u.next.capacity()).sum::<usize>();
+        self.nodes.capacity() * std::mem::size_of::<Node<T>>()
+            + links * std::mem::size_of::<Option<usize>>()
+            + self.free.capacity() * std::mem::size_of::<usize>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.nodes.capacity() - self.n
+    }
+}
+
+/// Yields the elements in increasing order.
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    u: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.nodes[self.u?];
+        self.u = node.next[0];
+        self.len -= 1;
+        node.x.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IntoIter<T> {
+    nodes: Vec<Node<T>>,
+    u: Option<usize>,
+    len: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &mut self.nodes[self.u?];
+        self.u = node.next[0];
+        self.len -= 1;
+        node.x.take()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T: Ord> IntoIterator for SkiplistSSet<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            u: self.nodes[SENTINEL].next[0],
+            nodes: self.nodes,
+            len: self.n,
+        }
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a SkiplistSSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> Extend<T> for SkiplistSSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<T: Ord> std::iter::FromIterator<T> for SkiplistSSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut skiplist_sset = Self::new();
+        skiplist_sset.extend(iter);
+        skiplist_sset
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn skiplist_sset_works() {
+        let mut skiplist_sset = SkiplistSSet::with_seed(42);
+        let mut expected = BTreeSet::new();
+        for i in 0..500 {
+            let x = (i * 37) % 101;
+            assert_eq!(skiplist_sset.add(x), expected.insert(x));
+        }
+        assert_eq!(skiplist_sset.size(), expected.len());
+        assert!(skiplist_sset.iter().eq(expected.iter()));
+        for i in 0..300 {
+            let x = (i * 13) % 120;
+            assert_eq!(skiplist_sset.remove(&x), expected.take(&x));
+            let y = (i * 7) % 130;
+            assert_eq!(skiplist_sset.find(&y), expected.range(y..).next());
+        }
+        assert!(skiplist_sset.iter().eq(expected.iter()));
+        assert_eq!(skiplist_sset.first(), expected.iter().next());
+        skiplist_sset.clear();
+        assert_eq!(skiplist_sset.find(&0), None);
+        assert!(skiplist_sset.add(3));
+        assert!(skiplist_sset.contains(&3));
+    }
+
+    #[test]
+    fn skiplist_sset_is_reproducible() {
+        let build = |seed| {
+            let mut skiplist_sset = SkiplistSSet::with_seed(seed);
+            skiplist_sset.extend([5, 1, 4, 2, 3, 9, 7, 8, 6].iter().copied());
+            format!("{:#?}", skiplist_sset)
+        };
+        assert_eq!(build(7), build(7));
+        let skiplist_sset = (1..=3).rev().collect::<SkiplistSSet<_>>();
+        assert_eq!(skiplist_sset.to_string(), "{1, 2, 3}");
+        assert_eq!(format!("{:?}", skiplist_sset), "{1, 2, 3}");
+        assert_eq!(Vec::from(skiplist_sset), [1, 2, 3]);
+    }
+
+    #[test]
+    fn skiplist_sset_reuses_freed_nodes() {
+        let mut skiplist_sset = SkiplistSSet::with_seed(1);
+        for round in 0..10 {
+            for x in 0..100 {
+                skiplist_sset.add(x + round);
+            }
+            for x in 0..100 {
+                assert_eq!(skiplist_sset.remove(&(x + round)), Some(x + round));
+            }
+        }
+        assert_eq!(skiplist_sset.size(), 0);
+        assert_eq!(skiplist_sset.nodes.len(), 101);
+    }
+}
@@ -1,74 +1,711 @@
-use std::{cell::RefCell, rc::Rc};
-#[derive(Debug)]
-struct SLList<T> {
+use crate::{
+    error::Error,
+    format,
+    interfaces::{List, Queue, Stack},
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    ptr::NonNull,
+};
+
+pub struct SLList<T> {
     n: usize,
     head: Link<T>,
     tail: Link<T>,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-type Link<T> = Option<Rc<RefCell<Node<T>>>>;
-#[derive(Debug)]
+type Link<T> = Option<NonNull<Node<T>>>;
+
 struct Node<T> {
     x: T,
     next: Link<T>,
 }
 
-impl<T: Default> SLList<T> {
+unsafe impl<T: Send> Send for SLList<T> {}
+unsafe impl<T: Sync> Sync for SLList<T> {}
+
+impl<T> SLList<T> {
     pub fn new() -> Self {
         Self {
             n: 0,
             head: None,
             tail: None,
+            marker: PhantomData,
         }
     }
     pub fn push(&mut self, x: T) {
         let u = Node::new(x);
-        u.borrow_mut().next = self.head.take();
-        self.head = Some(Rc::clone(&u));
+        // SAFETY: `u` was just allocated and is not shared yet.
+        unsafe { (*u.as_ptr()).next = self.head };
+        self.head = Some(u);
         if self.n == 0 {
-            self.tail = Some(Rc::clone(&u));
+            self.tail = Some(u);
         }
         self.n += 1;
     }
     pub fn pop(&mut self) -> Option<T> {
-        match self.head.take() {
-            Some(old_head) => {
-                self.head = old_head.borrow_mut().next.take();
-                self.n -= 1;
-                if self.n == 0 {
-                    self.tail = None;
-                }
-                Some(Rc::try_unwrap(old_head).ok().unwrap().into_inner().x)
+        self.head.map(|u| {
+            // SAFETY: `head` is owned by the list; once unlinked it is freed here.
+            let u = unsafe { Box::from_raw(u.as_ptr()) };
+            self.head = u.next;
+            self.n -= 1;
+            if self.n == 0 {
+                self.tail = None;
             }
-            None => None,
+            u.x
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: `head` is a live node owned by the list.
+        self.head.map(|u| unsafe { &(*u.as_ptr()).x })
+    }
+
+    fn get_node(&self, i: usize) -> NonNull<Node<T>> {
+        let mut u = self.head.unwrap();
+        for _ in 0..i {
+            // SAFETY: callers only ask for `i < n`, so the first `i + 1` nodes exist.
+            u = unsafe { (*u.as_ptr()).next.unwrap() };
+        }
+        u
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.n {
+            return None;
+        }
+        // SAFETY: `get_node(i)` is a live node owned by the list.
+        Some(unsafe { &(*self.get_node(i).as_ptr()).x })
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.n {
+            return None;
         }
+        // SAFETY: `get_node(i)` is a live node owned by the list.
+        Some(unsafe { &mut (*self.get_node(i).as_ptr()).x })
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
     }
 
-    pub fn remove(&mut self) -> Option<T> {
-        self.pop()
+    /// Inserts `x` at index `i`; O(1) at either end, otherwise O(i).
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
     }
 
-    pub fn add(&mut self, x: T) -> bool {
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        if i == 0 {
+            self.push(x);
+            return Ok(());
+        }
         let u = Node::new(x);
-        match self.tail.take() {
-            None => self.head = Some(Rc::clone(&u)),
-            Some(tail) => {
-                tail.borrow_mut().next = Some(Rc::clone(&u));
-            }
+        let prev = if i == self.n {
+            self.tail.unwrap()
+        } else {
+            self.get_node(i - 1)
+        };
+        // SAFETY: `prev` is a live node owned by the list and `u` is new.
+        unsafe {
+            (*u.as_ptr()).next = (*prev.as_ptr()).next;
+            (*prev.as_ptr()).next = Some(u);
+        }
+        if i == self.n {
+            self.tail = Some(u);
         }
-        self.tail = Some(Rc::clone(&u));
         self.n += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        if i == 0 {
+            return Ok(self.pop().unwrap());
+        }
+        let prev = self.get_node(i - 1);
+        // SAFETY: `prev` and its successor are live nodes owned by the list;
+        // the successor is unlinked and freed here.
+        let u = unsafe {
+            let u = Box::from_raw((*prev.as_ptr()).next.unwrap().as_ptr());
+            (*prev.as_ptr()).next = u.next;
+            u
+        };
+        if i == self.n - 1 {
+            self.tail = Some(prev);
+        }
+        self.n -= 1;
+        Ok(u.x)
+    }
+
+    pub fn remove_first(&mut self, x: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let i = self.iter().position(|y| y == x)?;
+        self.remove(i)
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|y| y == x)
+    }
+
+    pub fn reverse(&mut self) {
+        let mut prev = None;
+        let mut u = self.head;
+        while let Some(w) = u {
+            // SAFETY: `w` is a live node owned by the list.
+            unsafe {
+                u = (*w.as_ptr()).next;
+                (*w.as_ptr()).next = prev;
+            }
+            prev = Some(w);
+        }
+        std::mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Returns the element at index `(n - 1) / 2`, found without using `n`.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head?;
+        let mut fast = slow;
+        // SAFETY: every link reachable from `head` points to a live node.
+        unsafe {
+            while let Some(next) = (*fast.as_ptr()).next {
+                match (*next.as_ptr()).next {
+                    Some(next) => fast = next,
+                    None => break,
+                }
+                slow = (*slow.as_ptr()).next.unwrap();
+            }
+            Some(&(*slow.as_ptr()).x)
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Sorts the list stably, relinking nodes rather than moving elements.
+    ///
+    /// If `compare` panics, every element is still in the list, in some
+    /// unspecified order.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        // Bottom-up merge sort: merge runs of length k into runs of length
+        // 2k, relinking nodes in place, until a single run remains.
+        let mut k = 1;
+        loop {
+            let mut merge = Merge {
+                p: self.head.take(),
+                q: None,
+                psize: 0,
+                tail: None,
+                list: self,
+            };
+            let mut merges = 0;
+            // SAFETY: every node is unlinked from exactly one of the two runs
+            // being merged and relinked after `tail`, so each stays owned by
+            // the list exactly once.
+            unsafe {
+                while let Some(run) = merge.p {
+                    merges += 1;
+                    merge.q = Some(run);
+                    merge.psize = 0;
+                    while merge.psize < k {
+                        merge.psize += 1;
+                        merge.q = (*merge.q.unwrap().as_ptr()).next;
+                        if merge.q.is_none() {
+                            break;
+                        }
+                    }
+                    let mut qsize = k;
+                    loop {
+                        let e = match (merge.p, merge.q) {
+                            (Some(u), Some(w)) if merge.psize > 0 && qsize > 0 => {
+                                if compare(&(*u.as_ptr()).x, &(*w.as_ptr()).x) != Ordering::Greater
+                                {
+                                    merge.psize -= 1;
+                                    merge.p = (*u.as_ptr()).next;
+                                    u
+                                } else {
+                                    qsize -= 1;
+                                    merge.q = (*w.as_ptr()).next;
+                                    w
+                                }
+                            }
+                            (Some(u), _) if merge.psize > 0 => {
+                                merge.psize -= 1;
+                                merge.p = (*u.as_ptr()).next;
+                                u
+                            }
+                            (_, Some(w)) if qsize > 0 => {
+                                qsize -= 1;
+                                merge.q = (*w.as_ptr()).next;
+                                w
+                            }
+                            _ => break,
+                        };
+                        match merge.tail {
+                            None => merge.list.head = Some(e),
+                            Some(t) => (*t.as_ptr()).next = Some(e),
+                        }
+                        merge.tail = Some(e);
+                    }
+                    merge.p = merge.q;
+                }
+            }
+            // Every node is in the merged run now, so dropping `merge` just
+            // terminates it and sets `tail`.
+            drop(merge);
+            if merges <= 1 {
+                return;
+            }
+            k *= 2;
+        }
+    }
+
+    pub fn append(&mut self, other: &mut SLList<T>) {
+        let head = other.head.take();
+        match self.tail {
+            None => self.head = head,
+            // SAFETY: `tail` is a live node owned by the list.
+            Some(tail) => unsafe { (*tail.as_ptr()).next = head },
+        }
+        if let Some(tail) = other.tail.take() {
+            self.tail = Some(tail);
+        }
+        self.n += std::mem::replace(&mut other.n, 0);
+    }
+
+    pub fn split_off(&mut self, i: usize) -> SLList<T> {
+        assert!(
+            i <= self.n,
+            "split index (is {}) should be <= len (is {})",
+            i,
+            self.n
+        );
+        if i == 0 {
+            return std::mem::take(self);
+        }
+        let mut u = self.head.unwrap();
+        // SAFETY: the first `i` nodes are live nodes owned by the list.
+        let head = unsafe {
+            for _ in 0..i - 1 {
+                u = (*u.as_ptr()).next.unwrap();
+            }
+            (*u.as_ptr()).next.take()
+        };
+        let other = SLList {
+            n: self.n - i,
+            head,
+            tail: head.and(self.tail),
+            marker: PhantomData,
+        };
+        self.n = i;
+        self.tail = Some(u);
+        other
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            len: self.n,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head,
+            len: self.n,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Node<T> {
+    fn new(x: T) -> NonNull<Node<T>> {
+        NonNull::from(Box::leak(Box::new(Self { x, next: None })))
+    }
+}
+
+impl<T> Drop for SLList<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+/// The state of one pass of `SLList::sort_by`: the merged run ends at
+/// `tail`, then come the `psize` unmerged nodes from `p` and every node from
+/// `q` on. Dropping it links these back into one list, so a panicking
+/// comparison leaves every node reachable and `tail` correct.
+struct Merge<'a, T> {
+    list: &'a mut SLList<T>,
+    tail: Link<T>,
+    p: Link<T>,
+    q: Link<T>,
+    psize: usize,
+}
+
+impl<'a, T> Drop for Merge<'a, T> {
+    fn drop(&mut self) {
+        // SAFETY: the merged run, the `psize` nodes from `p` and the chain
+        // from `q` are disjoint and together hold every node of the list.
+        unsafe {
+            let mut rest = self.q;
+            if self.psize > 0 {
+                let mut u = self.p.unwrap();
+                for _ in 1..self.psize {
+                    u = (*u.as_ptr()).next.unwrap();
+                }
+                (*u.as_ptr()).next = rest;
+                rest = self.p;
+            }
+            let mut last = match self.tail {
+                None => {
+                    self.list.head = rest;
+                    self.list.head
+                }
+                Some(t) => {
+                    (*t.as_ptr()).next = rest;
+                    Some(t)
+                }
+            };
+            while let Some(w) = last.and_then(|u| (*u.as_ptr()).next) {
+                last = Some(w);
+            }
+            self.list.tail = last;
+        }
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the node chain from
+/// `head` and the node `tail` points at instead.
+impl<T: fmt::Debug> fmt::Debug for SLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let mut nodes = Vec::new();
+            // SAFETY: every link reachable from `head`, and `tail`, point to
+            // live nodes owned by this list.
+            let tail = unsafe {
+                let mut u = self.head;
+                while let Some(w) = u {
+                    let node = &*w.as_ptr();
+                    nodes.push(&node.x);
+                    u = node.next;
+                }
+                self.tail.map(|w| &(&*w.as_ptr()).x)
+            };
+            f.debug_struct("SLList")
+                .field("nodes", &nodes)
+                .field("tail", &tail)
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T> Default for SLList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for SLList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for SLList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for SLList<T> {}
+
+impl<T: PartialOrd> PartialOrd for SLList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for SLList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for SLList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for SLList<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for SLList<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
 
-        true
+impl<T: fmt::Display> fmt::Display for SLList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
     }
 }
 
-impl<T: Default> Node<T> {
-    fn new(x: T) -> Rc<RefCell<Node<T>>> {
-        Rc::new(RefCell::new(Self {
-            x,
-            next: Default::default(),
-        }))
+impl<T> From<Vec<T>> for SLList<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<SLList<T>> for Vec<T> {
+    fn from(xs: SLList<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for SLList<T> {
+    fn size(&self) -> usize {
+        SLList::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        SLList::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        SLList::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        SLList::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        SLList::remove(self, i)
+    }
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        SLList::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        SLList::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        SLList::try_remove(self, i)
+    }
+}
+
+impl<T> Queue<T> for SLList<T> {
+    fn push_back(&mut self, x: T) {
+        SLList::add(self, self.n, x);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        SLList::pop(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        SLList::peek(self)
+    }
+}
+
+impl<T> Stack<T> for SLList<T> {
+    fn push(&mut self, x: T) {
+        SLList::push(self, x)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        SLList::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        SLList::peek(self)
+    }
+}
+
+impl<T> MemoryUsage for SLList<T> {
+    fn heap_bytes(&self) -> usize {
+        self.n * std::mem::size_of::<Node<T>>()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.n
+    }
+
+    fn wasted_slots(&self) -> usize {
+        0
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|u| {
+            // SAFETY: the list is borrowed for `'a`, so its nodes stay alive.
+            let u = unsafe { &*u.as_ptr() };
+            self.next = u.next;
+            self.len -= 1;
+            &u.x
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|u| {
+            // SAFETY: the list is mutably borrowed for `'a`, and each node is
+            // visited at most once.
+            let u = unsafe { &mut *u.as_ptr() };
+            self.next = u.next;
+            self.len -= 1;
+            &mut u.x
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    list: SLList<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.n, Some(self.list.n))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for SLList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SLList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SLList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for SLList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for SLList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sllist = Self::new();
+        sllist.extend(iter);
+        sllist
     }
 }
 #[cfg(test)]
@@ -78,19 +715,180 @@ mod test {
     fn sllist_works() {
         let mut sllist = SLList::new();
         assert_eq!(sllist.pop(), None);
-        assert_eq!(sllist.remove(), None);
+        assert_eq!(sllist.remove(0), None);
         sllist.push(1);
         sllist.push(2);
         sllist.push(3);
-        sllist.add(4);
-        println!("{:?}", sllist);
-        // sllist: [3, 2, 1, 4]
+        sllist.add(3, 4);
+        assert_eq!(format!("{:?}", sllist), "[3, 2, 1, 4]");
+        assert_eq!(
+            format!("{:#?}", sllist)
+                .split_whitespace()
+                .collect::<String>(),
+            "SLList{nodes:[3,2,1,4,],tail:Some(4,),n:4,}"
+        );
         assert_eq!(sllist.pop(), Some(3));
         assert_eq!(sllist.pop(), Some(2));
         assert_eq!(sllist.pop(), Some(1));
         assert_eq!(sllist.pop(), Some(4));
         assert_eq!(sllist.pop(), None);
-        sllist.add(5);
+        sllist.add(0, 5);
         assert_eq!(sllist.pop(), Some(5));
     }
+
+    #[test]
+    fn sllist_iterators_work() {
+        let mut sllist: SLList<_> = (1..4).collect();
+        sllist.push(0);
+        // sllist: [0, 1, 2, 3]
+        assert_eq!(sllist.iter().len(), 4);
+        assert_eq!(sllist.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3]);
+        for x in &mut sllist {
+            *x *= 3;
+        }
+        sllist.extend(vec![12, 15]);
+        assert_eq!(sllist.into_iter().collect::<Vec<_>>(), [0, 3, 6, 9, 12, 15]);
+    }
+
+    #[test]
+    fn sllist_owns_its_values() {
+        fn assert_send_sync<S: Send + Sync>() {}
+        assert_send_sync::<SLList<String>>();
+
+        let counter = std::rc::Rc::new(());
+        let mut sllist = SLList::new();
+        for _ in 0..10 {
+            sllist.push(std::rc::Rc::clone(&counter));
+        }
+        sllist.pop();
+        assert_eq!(std::rc::Rc::strong_count(&counter), 10);
+        drop(sllist);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn sllist_append_and_split_off_work() {
+        let mut sllist: SLList<_> = (0..3).collect();
+        let mut other: SLList<_> = (3..6).collect();
+        sllist.append(&mut other);
+        assert_eq!(other.iter().len(), 0);
+        sllist.add(6, 6);
+        assert_eq!(
+            sllist.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 6]
+        );
+
+        let mut tail = sllist.split_off(4);
+        tail.add(3, 7);
+        sllist.add(4, -1);
+        assert_eq!(sllist.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, -1]);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), [4, 5, 6, 7]);
+
+        let empty = tail.split_off(4);
+        assert_eq!(empty.iter().len(), 0);
+        let all = tail.split_off(0);
+        assert_eq!(tail.iter().len(), 0);
+        assert_eq!(all.into_iter().collect::<Vec<_>>(), [4, 5, 6, 7]);
+
+        let mut empty = SLList::new();
+        empty.append(&mut sllist);
+        empty.add(5, 10);
+        assert_eq!(empty.into_iter().collect::<Vec<_>>(), [0, 1, 2, 3, -1, 10]);
+    }
+
+    #[test]
+    fn sllist_indexed_operations_work() {
+        let mut sllist: SLList<_> = (0..5).collect();
+        assert_eq!(sllist.size(), 5);
+        assert_eq!(sllist.peek(), Some(&0));
+        assert_eq!(sllist.get(3), Some(&3));
+        assert_eq!(sllist.get(5), None);
+        assert_eq!(sllist.set(2, 20), Some(2));
+        *sllist.get_mut(0).unwrap() = 10;
+        sllist.add(5, 5);
+        sllist.add(1, 1);
+        // sllist: [10, 1, 1, 20, 3, 4, 5]
+        assert_eq!(sllist.remove(6), Some(5));
+        sllist.add(6, 6);
+        assert_eq!(sllist.remove(3), Some(20));
+        // sllist: [10, 1, 1, 3, 4, 6]
+        assert!(sllist.contains(&1));
+        assert_eq!(sllist.remove_first(&1), Some(1));
+        assert_eq!(sllist.remove_first(&20), None);
+        assert!(!sllist.contains(&20));
+        assert_eq!(sllist.iter().copied().collect::<Vec<_>>(), [10, 1, 3, 4, 6]);
+        assert_eq!(sllist.middle(), Some(&3));
+        sllist.add(5, 7);
+        assert_eq!(sllist.middle(), Some(&3));
+
+        sllist.reverse();
+        sllist.add(6, -1);
+        assert_eq!(
+            sllist.iter().copied().collect::<Vec<_>>(),
+            [7, 6, 4, 3, 1, 10, -1]
+        );
+        assert_eq!(SLList::<i32>::new().middle(), None);
+    }
+
+    #[test]
+    fn sllist_sort_is_stable() {
+        let mut sllist: SLList<_> = vec![5, 3, 9, 1, 3, 7, 0, 8, 2, 3]
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x, i))
+            .collect();
+        sllist.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            sllist.iter().copied().collect::<Vec<_>>(),
+            [
+                (0, 6),
+                (1, 3),
+                (2, 8),
+                (3, 1),
+                (3, 4),
+                (3, 9),
+                (5, 0),
+                (7, 5),
+                (8, 7),
+                (9, 2)
+            ]
+        );
+        sllist.add(10, (10, 10));
+        assert_eq!(sllist.get(10), Some(&(10, 10)));
+
+        let mut sllist: SLList<_> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+        sllist.sort();
+        assert!(sllist.iter().copied().eq(0..1000));
+    }
+
+    #[test]
+    fn sllist_survives_a_panicking_comparison() {
+        for limit in [0, 1, 7, 40, 150] {
+            let mut sllist: SLList<_> = (0..50).map(|i| (i * 31) % 50).collect();
+            let mut comparisons = 0;
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                sllist.sort_by(|a, b| {
+                    comparisons += 1;
+                    if comparisons > limit {
+                        panic!("comparison limit reached");
+                    }
+                    a.cmp(b)
+                })
+            }));
+            assert!(result.is_err());
+            assert_eq!(sllist.size(), 50);
+            let mut xs = sllist.iter().copied().collect::<Vec<_>>();
+            xs.sort();
+            assert!(xs.into_iter().eq(0..50));
+            sllist.push_back(50);
+            assert_eq!(sllist.get(50), Some(&50));
+            assert_eq!(sllist.iter().count(), 51);
+        }
+    }
+
+    #[test]
+    fn sllist_drops_long_lists() {
+        let sllist: SLList<_> = (0..1_000_000).collect();
+        drop(sllist);
+    }
 }
@@ -0,0 +1,546 @@
+use crate::{
+    array_stack::{self, ArrayStack},
+    bounded_deque::{self, BoundedDeque},
+    error::Error,
+    format,
+    interfaces::List,
+    memory::MemoryUsage,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Index, IndexMut},
+};
+
+const MIN_BLOCK_SIZE: usize = 4;
+
+/// A list stored as circular blocks of `b` elements, where every block but
+/// the last is full and `b` is kept near √n. `get` and `set` locate
+/// index `i` at `i % b` in block `i / b`; `add` and `remove` shift within one
+/// block and then carry a single element across each later block, for
+/// O(b + n/b) = O(√n) time.
+#[derive(Clone)]
+pub struct TieredVector<T> {
+    b: usize,
+    n: usize,
+    blocks: ArrayStack<BoundedDeque<T>>,
+}
+
+impl<T> TieredVector<T> {
+    pub fn new() -> Self {
+        Self::with_block_size(MIN_BLOCK_SIZE)
+    }
+
+    fn with_block_size(b: usize) -> Self {
+        Self {
+            b,
+            n: 0,
+            blocks: ArrayStack::new(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.b
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            blocks: self.blocks.iter(),
+            front: [].iter().chain([].iter()),
+            back: [].iter().chain([].iter()),
+            len: self.n,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            blocks: self.blocks.iter_mut(),
+            front: [].iter_mut().chain([].iter_mut()),
+            back: [].iter_mut().chain([].iter_mut()),
+            len: self.n,
+        }
+    }
+
+    /// Returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.blocks.get(i / self.b)?.get(i % self.b)
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.blocks.get_mut(i / self.b)?.get_mut(i % self.b)
+    }
+
+    /// Replaces the element at index `i`, returning the old one, or `None` if `i >= size()`.
+    pub fn set(&mut self, i: usize, x: T) -> Option<T> {
+        self.try_set(i, x).ok()
+    }
+
+    pub fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        Error::check_index(i, self.n)?;
+        Ok(std::mem::replace(self.get_mut(i).unwrap(), x))
+    }
+
+    /// Inserts `x` at index `i` in O(√n) time.
+    ///
+    /// Panics if `i > size()`; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, i: usize, x: T) {
+        if let Err(e) = self.try_add(i, x) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        Error::check_insert(i, self.n)?;
+        if self.n == self.blocks.size() * self.b {
+            self.blocks
+                .try_add(self.blocks.size(), BoundedDeque::new(self.b))?;
+        }
+        // Each full block passes its last element on to the front of the next.
+        let (mut k, mut j, mut x) = (i / self.b, i % self.b, x);
+        loop {
+            let block = self.blocks.get_mut(k).unwrap();
+            let carry = if block.is_full() {
+                block.pop_back()
+            } else {
+                None
+            };
+            block.add(j, x);
+            match carry {
+                Some(y) => {
+                    x = y;
+                    k += 1;
+                    j = 0;
+                }
+                None => break,
+            }
+        }
+        self.n += 1;
+        if self.blocks.size() > 2 * self.b {
+            self.rebuild(2 * self.b);
+        }
+        Ok(())
+    }
+
+    /// Removes and returns the element at index `i`, or `None` if `i >= size()`.
+    pub fn remove(&mut self, i: usize) -> Option<T> {
+        self.try_remove(i).ok()
+    }
+
+    pub fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        Error::check_remove(i, self.n)?;
+        let k = i / self.b;
+        let x = self.blocks.get_mut(k).unwrap().remove(i % self.b).unwrap();
+        // Each later block passes its first element back to the previous one.
+        for m in k + 1..self.blocks.size() {
+            let y = self.blocks.get_mut(m).unwrap().pop_front().unwrap();
+            self.blocks.get_mut(m - 1).unwrap().push_back(y);
+        }
+        let r = self.blocks.size();
+        if self.blocks.get(r - 1).unwrap().size() == 0 {
+            self.blocks.remove(r - 1);
+        }
+        self.n -= 1;
+        if self.b > MIN_BLOCK_SIZE && 4 * self.blocks.size() < self.b {
+            self.rebuild(self.b / 2);
+        }
+        Ok(x)
+    }
+
+    /// Redistributes the elements into blocks of size `b`.
+    fn rebuild(&mut self, b: usize) {
+        let old = std::mem::replace(self, TieredVector::with_block_size(b));
+        self.extend(old);
+    }
+}
+
+impl<T> Default for TieredVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats as the list of elements; `{:#?}` shows the backing layout instead.
+impl<T: fmt::Debug> fmt::Debug for TieredVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("TieredVector")
+                .field("b", &self.b)
+                .field("blocks", &self.blocks.iter().collect::<Vec<_>>())
+                .field("n", &self.n)
+                .finish()
+        } else {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for TieredVector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for TieredVector<T> {}
+
+impl<T: PartialOrd> PartialOrd for TieredVector<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for TieredVector<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for TieredVector<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.n);
+        for x in self.iter() {
+            x.hash(state);
+        }
+    }
+}
+
+impl<T> Index<usize> for TieredVector<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        let len = self.n;
+        match self.get(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for TieredVector<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        let len = self.n;
+        match self.get_mut(i) {
+            Some(x) => x,
+            None => panic!("{}", Error::IndexOutOfBounds { index: i, len }),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for TieredVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format::display_list(f, self.iter())
+    }
+}
+
+impl<T> From<Vec<T>> for TieredVector<T> {
+    fn from(xs: Vec<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> From<TieredVector<T>> for Vec<T> {
+    fn from(xs: TieredVector<T>) -> Self {
+        xs.into_iter().collect()
+    }
+}
+
+impl<T> List<T> for TieredVector<T> {
+    fn size(&self) -> usize {
+        TieredVector::size(self)
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        TieredVector::get(self, i)
+    }
+
+    fn set(&mut self, i: usize, x: T) -> Option<T> {
+        TieredVector::set(self, i, x)
+    }
+
+    fn add(&mut self, i: usize, x: T) {
+        TieredVector::add(self, i, x)
+    }
+
+    fn remove(&mut self, i: usize) -> Option<T> {
+        TieredVector::remove(self, i)
+    }
+
+    fn try_set(&mut self, i: usize, x: T) -> Result<T, Error> {
+        TieredVector::try_set(self, i, x)
+    }
+
+    fn try_add(&mut self, i: usize, x: T) -> Result<(), Error> {
+        TieredVector::try_add(self, i, x)
+    }
+
+    fn try_remove(&mut self, i: usize) -> Result<T, Error> {
+        TieredVector::try_remove(self, i)
+    }
+}
+
+impl<T> MemoryUsage for TieredVector<T> {
+    fn heap_bytes(&self) -> usize {
+        self.allocated_slots() * std::mem::size_of::<T>() + self.blocks.heap_bytes()
+    }
+
+    fn allocated_slots(&self) -> usize {
+        self.blocks.size() * self.b
+    }
+
+    fn wasted_slots(&self) -> usize {
+        self.allocated_slots() - self.n
+    }
+}
+
+pub struct Iter<'a, T> {
+    blocks: array_stack::Iter<'a, BoundedDeque<T>>,
+    front: bounded_deque::Iter<'a, T>,
+    back: bounded_deque::Iter<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => self.front = block.iter(),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => self.back = block.iter(),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    blocks: array_stack::IterMut<'a, BoundedDeque<T>>,
+    front: bounded_deque::IterMut<'a, T>,
+    back: bounded_deque::IterMut<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => self.front = block.iter_mut(),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => self.back = block.iter_mut(),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+pub struct IntoIter<T> {
+    blocks: array_stack::IntoIter<BoundedDeque<T>>,
+    front: std::vec::IntoIter<T>,
+    back: std::vec::IntoIter<T>,
+    len: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.front.next() {
+                return Some(x);
+            }
+            match self.blocks.next() {
+                Some(block) => self.front = block.into_vec().into_iter(),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        loop {
+            if let Some(x) = self.back.next_back() {
+                return Some(x);
+            }
+            match self.blocks.next_back() {
+                Some(block) => self.back = block.into_vec().into_iter(),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for TieredVector<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            blocks: self.blocks.into_iter(),
+            front: Vec::new().into_iter(),
+            back: Vec::new().into_iter(),
+            len: self.n,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TieredVector<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut TieredVector<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for TieredVector<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(self.n, x);
+        }
+    }
+}
+
+impl<T> std::iter::FromIterator<T> for TieredVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tiered_vector = Self::new();
+        tiered_vector.extend(iter);
+        tiered_vector
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn tiered_vector_works() {
+        let mut tiered_vector = TieredVector::new();
+        let mut expected = Vec::new();
+        for i in 0..200 {
+            let k = (i * 7) % (expected.len() + 1);
+            tiered_vector.add(k, i);
+            expected.insert(k, i);
+        }
+        assert_eq!(
+            tiered_vector.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(tiered_vector.block_size(), 16);
+        for (i, x) in expected.iter().enumerate() {
+            assert_eq!(tiered_vector.get(i), Some(x));
+        }
+        while !expected.is_empty() {
+            let k = (expected.len() * 5) % (expected.len() + 3) % expected.len();
+            assert_eq!(tiered_vector.remove(k), Some(expected.remove(k)));
+        }
+        assert_eq!(tiered_vector.size(), 0);
+        assert_eq!(tiered_vector.block_size(), MIN_BLOCK_SIZE);
+    }
+
+    #[test]
+    fn tiered_vector_iterators_work() {
+        let mut tiered_vector = (0..30).collect::<TieredVector<_>>();
+        tiered_vector.add(0, -1);
+        // every block now wraps around
+        for x in tiered_vector.iter_mut() {
+            *x *= 2;
+        }
+        let expected = (-1..30).map(|x| x * 2).collect::<Vec<_>>();
+        assert_eq!(
+            tiered_vector.iter().rev().copied().collect::<Vec<_>>(),
+            expected.iter().rev().copied().collect::<Vec<_>>()
+        );
+        let mut into_iter = tiered_vector.into_iter();
+        assert_eq!(into_iter.len(), 31);
+        assert_eq!(into_iter.next_back(), Some(58));
+        assert_eq!(into_iter.collect::<Vec<_>>(), expected[..30]);
+    }
+}